@@ -0,0 +1,138 @@
+use crate::{grid::Grid, registry::Part};
+use serde::Serialize;
+use std::fmt;
+
+/// A puzzle's answer, keeping its shape rather than just its text.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", content = "value", rename_all = "lowercase")]
+pub enum Answer {
+    Integer(i64),
+    String(String),
+    /// Written like `x,y` or `x,y,size`.
+    Coord(Vec<i64>),
+    /// Rows of text, written one per line.
+    Grid(Vec<String>),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Integer(n) => n.fmt(f),
+            Answer::String(s) => s.fmt(f),
+            Answer::Coord(coord) => {
+                let parts: Vec<_> = coord.iter().map(i64::to_string).collect();
+                parts.join(",").fmt(f)
+            }
+            Answer::Grid(rows) => rows.join("\n").fmt(f),
+        }
+    }
+}
+
+macro_rules! integer_answers {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(n: $int) -> Self {
+                    Answer::Integer(n as i64)
+                }
+            }
+
+            impl From<($int, $int)> for Answer {
+                fn from((x, y): ($int, $int)) -> Self {
+                    Answer::Coord(vec![x as i64, y as i64])
+                }
+            }
+
+            impl From<($int, $int, $int)> for Answer {
+                fn from((x, y, z): ($int, $int, $int)) -> Self {
+                    Answer::Coord(vec![x as i64, y as i64, z as i64])
+                }
+            }
+        )*
+    };
+}
+
+integer_answers!(i32, u32, i64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::String(s)
+    }
+}
+
+impl<T: fmt::Display> From<Grid<T>> for Answer {
+    fn from(grid: Grid<T>) -> Self {
+        Answer::Grid(grid.to_string().lines().map(String::from).collect())
+    }
+}
+
+/// One of the intermediate values an answer was worked out from, shown by
+/// `run --explain` so the answer can be checked by hand.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Fact {
+    pub name: String,
+    pub value: Answer,
+}
+
+impl Fact {
+    pub fn new(name: impl Into<String>, value: impl Into<Answer>) -> Self {
+        Fact {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+}
+
+impl fmt::Display for Fact {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.name, self.value)
+    }
+}
+
+/// One part's answer and how long it took, as written by `run --format
+/// json`.
+#[derive(Clone, Debug, Serialize)]
+pub struct Record {
+    pub day: u32,
+    pub part: Part,
+    pub answer: Answer,
+    pub elapsed_ms: f64,
+    /// Only written with `--explain`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub explanation: Vec<Fact>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn displays_like_the_plain_answers() {
+        assert_eq!(Answer::from(-12).to_string(), "-12");
+        assert_eq!(Answer::from((90, 269, 16)).to_string(), "90,269,16");
+        let grid = Grid::from_fn(2, 2, |(x, y)| if x == y { '#' } else { '.' });
+        assert_eq!(Answer::from(grid).to_string(), "#.\n.#");
+    }
+
+    #[test]
+    fn serializes_with_its_type() {
+        let record = Record {
+            day: 11,
+            part: Part::One,
+            answer: Answer::from((33, 45)),
+            elapsed_ms: 1.5,
+            explanation: Vec::new(),
+        };
+        assert_eq!(
+            serde_json::to_string(&record).unwrap(),
+            r#"{"day":11,"part":1,"answer":{"type":"coord","value":[33,45]},"elapsed_ms":1.5}"#
+        );
+        let record = Record {
+            explanation: vec![Fact::new("power", 30)],
+            ..record
+        };
+        assert!(serde_json::to_string(&record).unwrap().ends_with(
+            r#""explanation":[{"name":"power","value":{"type":"integer","value":30}}]}"#
+        ));
+    }
+}
@@ -0,0 +1,244 @@
+use crate::{
+    parse,
+    registry::{Part, Puzzle},
+    worker::{self, Failure},
+};
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+pub struct Options {
+    /// How many times to repeat parsing and both parts.
+    pub runs: usize,
+    /// Stop repeating once this much time has been spent on a day, as long as
+    /// it has been run at least once.
+    pub budget: Duration,
+    /// Give up on a day if a single parse or part takes longer than this.
+    pub timeout: Duration,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            runs: 10,
+            budget: Duration::from_secs(30),
+            timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+/// The distribution of a step's running time over repeated runs.
+#[derive(Clone, Copy, Debug)]
+pub struct Stats {
+    pub runs: usize,
+    pub median: Duration,
+    /// Median absolute deviation from the median.
+    pub spread: Duration,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        let mid = median(&mut samples);
+        let mut deviations = samples
+            .iter()
+            .map(|&sample| sample.abs_diff(mid))
+            .collect::<Vec<_>>();
+        Stats {
+            runs: samples.len(),
+            median: mid,
+            spread: median(&mut deviations),
+        }
+    }
+}
+
+fn median(samples: &mut [Duration]) -> Duration {
+    samples.sort();
+    let mid = samples.len() / 2;
+    if samples.len().is_multiple_of(2) {
+        (samples[mid - 1] + samples[mid]) / 2
+    } else {
+        samples[mid]
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Measurement {
+    Done(Stats),
+    /// A single run took longer than the timeout.
+    TimedOut,
+    /// The step panicked.
+    Panicked,
+    /// Never reached because an earlier step timed out.
+    Skipped,
+}
+
+impl Measurement {
+    pub fn median(&self) -> Option<Duration> {
+        match self {
+            Measurement::Done(stats) => Some(stats.median),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Measurement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Measurement::Done(stats) => {
+                let precision = precision(stats.median);
+                write!(
+                    f,
+                    "{:.*} ±{:.*}",
+                    precision,
+                    as_ms(stats.median),
+                    precision,
+                    as_ms(stats.spread)
+                )
+            }
+            Measurement::TimedOut => "(timed out)".fmt(f),
+            Measurement::Panicked => "(panicked)".fmt(f),
+            Measurement::Skipped => "-".fmt(f),
+        }
+    }
+}
+
+fn as_ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// How many decimal places of milliseconds are worth showing for `duration`.
+fn precision(duration: Duration) -> usize {
+    match as_ms(duration) {
+        ms if ms < 10.0 => 3,
+        ms if ms < 1000.0 => 1,
+        _ => 0,
+    }
+}
+
+pub struct Timings {
+    pub day: u32,
+    pub parse: Measurement,
+    pub part1: Measurement,
+    pub part2: Measurement,
+}
+
+impl Timings {
+    pub fn total(&self) -> Option<Duration> {
+        Some(self.parse.median()? + self.part1.median()? + self.part2.median()?)
+    }
+
+    /// This day's row in the runtime table.
+    pub fn row(&self) -> String {
+        let total = self.total().map_or_else(
+            || "-".to_string(),
+            |total| format!("{:.*}", precision(total), as_ms(total)),
+        );
+        format!(
+            "| {} | {} | {} | {} | {} |",
+            self.day, self.parse, self.part1, self.part2, total
+        )
+    }
+}
+
+pub const TABLE_HEADER: &str = "\
+| Day | Parse (ms) | Part 1 (ms) | Part 2 (ms) | Total (ms) |
+| --: | ---------: | ----------: | ----------: | ---------: |";
+
+/// Times parsing and both parts of `puzzle` separately, or fails without
+/// timing anything if `input` can't be parsed.
+pub fn bench(puzzle: &'static Puzzle, input: String, options: &Options) -> parse::Result<Timings> {
+    puzzle.parse(&input)?;
+    let (runs, budget) = (options.runs, options.budget);
+    let (elapsed, failure) = worker::collect(options.timeout, move |tx| {
+        let start = Instant::now();
+        for _ in 0..runs {
+            let now = Instant::now();
+            let parsed = puzzle.parse(&input).expect("input was already parsed");
+            if tx.send(now.elapsed()).is_err() {
+                return;
+            }
+            for &part in &Part::BOTH {
+                let now = Instant::now();
+                puzzle.solve(&*parsed, part);
+                if tx.send(now.elapsed()).is_err() {
+                    return;
+                }
+            }
+            if start.elapsed() > budget {
+                return;
+            }
+        }
+    });
+
+    let failed_step = elapsed.len() % 3;
+    let mut samples = vec![Vec::new(), Vec::new(), Vec::new()];
+    for (i, elapsed) in elapsed.into_iter().enumerate() {
+        samples[i % 3].push(elapsed);
+    }
+    let mut measurements = samples
+        .into_iter()
+        .enumerate()
+        .map(|(step, samples)| match failure {
+            Some(Failure::TimedOut) if step == failed_step => Measurement::TimedOut,
+            Some(Failure::Panicked(_)) if step == failed_step => Measurement::Panicked,
+            Some(_) if samples.is_empty() => Measurement::Skipped,
+            _ => Measurement::Done(Stats::from_samples(samples)),
+        });
+    Ok(Timings {
+        day: puzzle.day,
+        parse: measurements.next().unwrap(),
+        part1: measurements.next().unwrap(),
+        part2: measurements.next().unwrap(),
+    })
+}
+
+/// Replaces the rows of the runtime table in `text` for the days in
+/// `timings`, keeping rows for other days.
+///
+/// The table is found between `<!-- runtime -->` and `<!-- /runtime -->`
+/// markers, which are appended if `text` doesn't contain them yet.
+pub fn update_table(text: &str, timings: &[Timings]) -> String {
+    const START: &str = "<!-- runtime -->";
+    const END: &str = "<!-- /runtime -->";
+
+    let (before, table, after) = match (text.find(START), text.find(END)) {
+        (Some(start), Some(end)) if start < end => (
+            &text[..start],
+            &text[start + START.len()..end],
+            &text[end + END.len()..],
+        ),
+        _ => (text, "", "\n"),
+    };
+
+    let mut rows: Vec<(u32, String)> = table
+        .lines()
+        .filter_map(|line| {
+            let day = line.trim_start_matches('|').split('|').next()?;
+            Some((day.trim().parse().ok()?, line.to_string()))
+        })
+        .filter(|(day, _)| timings.iter().all(|t| t.day != *day))
+        .collect();
+    rows.extend(timings.iter().map(|t| (t.day, t.row())));
+    rows.sort_by_key(|&(day, _)| day);
+
+    let mut updated = before.to_string();
+    if !updated.is_empty() && !updated.ends_with("\n\n") {
+        updated.push_str(if updated.ends_with('\n') {
+            "\n"
+        } else {
+            "\n\n"
+        });
+    }
+    updated.push_str(START);
+    updated.push('\n');
+    updated.push_str(TABLE_HEADER);
+    updated.push('\n');
+    for (_, row) in rows {
+        updated.push_str(&row);
+        updated.push('\n');
+    }
+    updated.push_str(END);
+    updated.push_str(after);
+    updated
+}
@@ -0,0 +1,690 @@
+use advent_of_code_2018::{
+    answer::{Answer, Record},
+    bench::{self, Options},
+    differential, generate, parallel,
+    params::Overrides,
+    parse::ParseError,
+    profile::{self, Profile},
+    registry::{self, Part, Puzzle, PUZZLES},
+    snapshot,
+    verify::{self, Answers},
+    visualize::{self, Format},
+};
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use std::{
+    error::Error,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    process, thread,
+    time::{Duration, Instant},
+};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+fn days_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("day")
+        .help("Days to run; all of them if none are given")
+        .multiple(true)
+        .validator(validate_day)
+}
+
+fn input_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("input")
+        .long("input")
+        .short("i")
+        .takes_value(true)
+        .help("Read puzzle input from this file, or stdin if '-', instead of the bundled input")
+}
+
+fn timeout_arg<'a, 'b>(help: &'a str) -> Arg<'a, 'b> {
+    Arg::with_name("timeout")
+        .long("timeout")
+        .takes_value(true)
+        .default_value("60")
+        .validator(validate_number)
+        .help(help)
+}
+
+fn validate_day(day: String) -> std::result::Result<(), String> {
+    day.parse()
+        .ok()
+        .and_then(registry::find)
+        .map(|_| ())
+        .ok_or_else(|| format!("no solution for day {}", day))
+}
+
+fn validate_generated_day(day: String) -> std::result::Result<(), String> {
+    day.parse()
+        .ok()
+        .and_then(generate::find)
+        .map(|_| ())
+        .ok_or_else(|| {
+            let days: Vec<_> = generate::GENERATORS
+                .iter()
+                .map(|generator| generator.day.to_string())
+                .collect();
+            format!(
+                "no generator for day {}; there are generators for days {}",
+                day,
+                days.join(", ")
+            )
+        })
+}
+
+fn validate_seed(seed: String) -> std::result::Result<(), String> {
+    seed.parse::<u64>()
+        .map(|_| ())
+        .map_err(|_| format!("expected a number, got {}", seed))
+}
+
+fn validate_override(assignment: String) -> std::result::Result<(), String> {
+    Overrides::default().set(&assignment)
+}
+
+fn validate_number(n: String) -> std::result::Result<(), String> {
+    match n.parse::<u64>() {
+        Ok(n) if n > 0 => Ok(()),
+        _ => Err(format!("expected a positive number, got {}", n)),
+    }
+}
+
+fn app() -> App<'static, 'static> {
+    App::new("aoc")
+        .about("Advent of Code 2018 solutions")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .setting(AppSettings::VersionlessSubcommands)
+        .subcommand(
+            SubCommand::with_name("run")
+                .about("Prints the answers for one or more days")
+                .arg(days_arg())
+                .arg(input_arg())
+                .arg(
+                    Arg::with_name("part")
+                        .long("part")
+                        .short("p")
+                        .takes_value(true)
+                        .possible_values(&["1", "2"])
+                        .help("Only solve this part"),
+                )
+                .arg(
+                    Arg::with_name("format")
+                        .long("format")
+                        .short("f")
+                        .takes_value(true)
+                        .possible_values(&["text", "json"])
+                        .default_value("text")
+                        .help("Print answers as text, or as one JSON record per line with timings"),
+                )
+                .arg(
+                    Arg::with_name("explain")
+                        .long("explain")
+                        .short("e")
+                        .help("Also print the intermediate values each answer was worked out from"),
+                )
+                .arg(
+                    Arg::with_name("set")
+                        .long("set")
+                        .short("s")
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1)
+                        .value_name("[dayNN.]NAME=VALUE")
+                        .validator(validate_override)
+                        .help("Override a puzzle parameter; can be given more than once"),
+                )
+                .arg(
+                    Arg::with_name("config")
+                        .long("config")
+                        .short("c")
+                        .takes_value(true)
+                        .help("Read parameter overrides from this file, under [dayNN] headings"),
+                )
+                .arg(
+                    Arg::with_name("snapshots")
+                        .long("snapshots")
+                        .takes_value(true)
+                        .value_name("DIR")
+                        .help(
+                            "Save long-running parts' progress in this directory, \
+                             and resume from what an interrupted run saved there",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("every")
+                        .long("every")
+                        .takes_value(true)
+                        .value_name("SECONDS")
+                        .requires("snapshots")
+                        .validator(validate_number)
+                        .help("How often to save progress [default: 30]"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("params")
+                .about("Lists the puzzle parameters that run --set can override")
+                .arg(days_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("all")
+                .about("Runs every day at once on a thread pool and prints a summary")
+                .arg(days_arg())
+                .arg(
+                    Arg::with_name("threads")
+                        .long("threads")
+                        .short("j")
+                        .takes_value(true)
+                        .validator(validate_number)
+                        .help("How many days to run at once [default: one per CPU]"),
+                )
+                .arg(timeout_arg("Give up on a day after this many seconds")),
+        )
+        .subcommand(
+            SubCommand::with_name("bench")
+                .about("Times parsing and each part for one or more days")
+                .arg(days_arg())
+                .arg(input_arg())
+                .arg(
+                    Arg::with_name("runs")
+                        .long("runs")
+                        .short("n")
+                        .takes_value(true)
+                        .default_value("10")
+                        .validator(validate_number)
+                        .help("How many times to run each day"),
+                )
+                .arg(timeout_arg(
+                    "Give up on a day after a single step takes this many seconds",
+                ))
+                .arg(
+                    Arg::with_name("write")
+                        .long("write")
+                        .conflicts_with("input")
+                        .help("Update the runtime tables in README.md and runtime.md"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("verify")
+                .about("Checks answers against the expected answers for their input")
+                .arg(days_arg())
+                .arg(input_arg())
+                .arg(
+                    Arg::with_name("answers")
+                        .long("answers")
+                        .short("a")
+                        .takes_value(true)
+                        .requires("input")
+                        .help("Read the expected answers for --input from this file"),
+                )
+                .arg(timeout_arg("Give up on a part after this many seconds")),
+        )
+        .subcommand(
+            SubCommand::with_name("profiles")
+                .about("Solves one or more days for every input profile and prints an answer matrix")
+                .arg(days_arg())
+                .arg(
+                    Arg::with_name("dir")
+                        .long("dir")
+                        .takes_value(true)
+                        .default_value("inputs")
+                        .help("Where the profiles are, as <dir>/<profile>/dayNN.txt"),
+                )
+                .arg(
+                    Arg::with_name("profile")
+                        .long("profile")
+                        .short("P")
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1)
+                        .help("Only use this profile; can be given more than once"),
+                )
+                .arg(timeout_arg("Give up on a part after this many seconds")),
+        )
+        .subcommand(
+            SubCommand::with_name("diff")
+                .about("Checks optimized solutions against the straightforward ones they replaced")
+                .arg(days_arg())
+                .arg(
+                    Arg::with_name("seeds")
+                        .long("seeds")
+                        .takes_value(true)
+                        .default_value("20")
+                        .validator(validate_seed)
+                        .help("How many generated inputs to try, besides the bundled one"),
+                )
+                .arg(
+                    Arg::with_name("size")
+                        .long("size")
+                        .short("n")
+                        .takes_value(true)
+                        .validator(validate_number)
+                        .help("The size of the generated inputs [default: like real inputs]"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("generate")
+                .about("Writes a random puzzle input for a day")
+                .arg(
+                    Arg::with_name("day")
+                        .required(true)
+                        .validator(validate_generated_day),
+                )
+                .arg(
+                    Arg::with_name("seed")
+                        .long("seed")
+                        .short("s")
+                        .takes_value(true)
+                        .default_value("0")
+                        .validator(validate_seed)
+                        .help("The same seed and size always give the same input"),
+                )
+                .arg(
+                    Arg::with_name("size")
+                        .long("size")
+                        .short("n")
+                        .takes_value(true)
+                        .validator(validate_number)
+                        .help("How many claims, stars, cells along a side, ... [default: like real inputs]"),
+                )
+                .arg(
+                    Arg::with_name("output")
+                        .long("output")
+                        .short("o")
+                        .takes_value(true)
+                        .help("Where to write the input [default: stdout]"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("render")
+                .about("Writes a day's visualization")
+                .arg(Arg::with_name("day").required(true).validator(validate_day))
+                .arg(input_arg())
+                .arg(
+                    Arg::with_name("format")
+                        .long("format")
+                        .short("f")
+                        .takes_value(true)
+                        .possible_values(&Format::NAMES)
+                        .help(
+                            "An animated GIF, a PNG of the last frame, or live terminal output \
+                             [default: from the output's extension, or gif]",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("output")
+                        .long("output")
+                        .short("o")
+                        .takes_value(true)
+                        .help("Where to write the image [default: dayXX.gif or .png; stdout for ansi]"),
+                )
+                .arg(
+                    Arg::with_name("scale")
+                        .long("scale")
+                        .takes_value(true)
+                        .default_value("1")
+                        .validator(validate_number)
+                        .help("Draw each cell this many pixels wide and high"),
+                )
+                .arg(
+                    Arg::with_name("delay")
+                        .long("delay")
+                        .takes_value(true)
+                        .validator(validate_number)
+                        .help("Show each frame for this many milliseconds [default: the day's own]"),
+                )
+                .arg(
+                    Arg::with_name("frames")
+                        .long("frames")
+                        .takes_value(true)
+                        .validator(validate_number)
+                        .help("Stop after this many frames"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("step")
+                .about("Steps through a day's simulation in the terminal")
+                .arg(Arg::with_name("day").required(true).validator(validate_day))
+                .arg(input_arg())
+                .arg(
+                    Arg::with_name("delay")
+                        .long("delay")
+                        .takes_value(true)
+                        .validator(validate_number)
+                        .help("Play one step per this many milliseconds [default: the day's own]"),
+                ),
+        )
+}
+
+/// The puzzles named on the command line, in calendar order.
+fn selected_puzzles(matches: &ArgMatches) -> Result<Vec<&'static Puzzle>> {
+    let puzzles: Vec<_> = match matches.values_of("day") {
+        Some(days) => {
+            let mut days: Vec<u32> = days.map(|day| day.parse().unwrap()).collect();
+            days.sort();
+            days.dedup();
+            days.into_iter().filter_map(registry::find).collect()
+        }
+        None => PUZZLES.iter().collect(),
+    };
+    if puzzles.len() > 1 && matches.is_present("input") {
+        return Err("--input can only be used with a single day".into());
+    }
+    Ok(puzzles)
+}
+
+fn invalid_input(puzzle: &Puzzle, error: ParseError) -> Box<dyn Error> {
+    format!("invalid input for day {}: {}", puzzle.day, error).into()
+}
+
+fn run(matches: &ArgMatches) -> Result<()> {
+    let parts = match matches.value_of("part") {
+        Some("1") => vec![Part::One],
+        Some("2") => vec![Part::Two],
+        _ => Part::BOTH.to_vec(),
+    };
+    let puzzles = selected_puzzles(matches)?;
+    let mut overrides = Overrides::default();
+    if let Some(path) = matches.value_of("config") {
+        let text = fs::read_to_string(path)?;
+        let config = Overrides::parse(&text).map_err(|error| format!("{}: {}", path, error))?;
+        overrides.extend(config);
+    }
+    for assignment in matches.values_of("set").into_iter().flatten() {
+        overrides.set(assignment)?;
+    }
+    let declared: Vec<_> = puzzles.iter().map(|p| (p.day, p.params)).collect();
+    overrides.check(&declared)?;
+    let snapshots = matches.value_of("snapshots").map(|dir| snapshot::Options {
+        dir: PathBuf::from(dir),
+        every: Duration::from_secs(matches.value_of("every").unwrap_or("30").parse().unwrap()),
+    });
+    for puzzle in puzzles {
+        let params = overrides.for_day(puzzle.day, puzzle.params);
+        let text = puzzle.input(matches.value_of("input"))?;
+        let input = puzzle
+            .parse_with(&text, &params)
+            .map_err(|error| invalid_input(puzzle, error))?;
+        let values: Vec<_> = puzzle
+            .params
+            .iter()
+            .map(|param| format!("{}={}", param.name, params.get(param)))
+            .collect();
+        let fingerprint = snapshot::fingerprint(
+            Some(&text[..])
+                .into_iter()
+                .chain(values.iter().map(|v| &v[..])),
+        );
+        for &part in &parts {
+            let start = Instant::now();
+            let resumed = match &snapshots {
+                Some(options) => puzzle.resume(&*input, fingerprint, part, options)?,
+                None => None,
+            };
+            let answer = resumed.unwrap_or_else(|| puzzle.solve(&*input, part));
+            let elapsed = start.elapsed();
+            let explanation = if matches.is_present("explain") {
+                puzzle.explain(&*input, part)
+            } else {
+                Vec::new()
+            };
+            if matches.value_of("format") == Some("json") {
+                let record = Record {
+                    day: puzzle.day,
+                    part,
+                    answer,
+                    elapsed_ms: elapsed.as_secs_f64() * 1000.0,
+                    explanation,
+                };
+                println!("{}", serde_json::to_string(&record)?);
+            } else {
+                let separator = match answer {
+                    Answer::Grid(_) => '\n',
+                    _ => ' ',
+                };
+                println!(
+                    "Day {:02} part {}:{}{}",
+                    puzzle.day, part, separator, answer
+                );
+                for fact in &explanation {
+                    println!("    {}", fact);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn params(matches: &ArgMatches) -> Result<()> {
+    println!("| Day | Parameter | Default | Description |");
+    println!("| --: | --------- | ------: | ----------- |");
+    for puzzle in selected_puzzles(matches)? {
+        for param in puzzle.params {
+            println!(
+                "| {} | {} | {} | {} |",
+                puzzle.day, param.name, param.default, param.doc
+            );
+        }
+    }
+    Ok(())
+}
+
+fn all(matches: &ArgMatches) -> Result<()> {
+    let threads = match matches.value_of("threads") {
+        Some(threads) => threads.parse()?,
+        None => thread::available_parallelism().map_or(1, |n| n.get()),
+    };
+    let timeout = Duration::from_secs(matches.value_of("timeout").unwrap().parse()?);
+    let jobs = selected_puzzles(matches)?
+        .into_iter()
+        .map(|puzzle| Ok((puzzle, puzzle.input(None)?)))
+        .collect::<io::Result<_>>()?;
+    let reports = parallel::run_all(jobs, threads, timeout);
+    println!("{}", parallel::TABLE_HEADER);
+    for report in &reports {
+        println!("{}", report.row());
+    }
+    let failed = reports.iter().filter(|report| !report.is_ok()).count();
+    if failed > 0 {
+        return Err(format!("{} of {} days failed", failed, reports.len()).into());
+    }
+    Ok(())
+}
+
+fn bench(matches: &ArgMatches) -> Result<()> {
+    if cfg!(debug_assertions) {
+        eprintln!("warning: benchmarking a debug build; use `cargo run --release`");
+    }
+    let options = Options {
+        runs: matches.value_of("runs").unwrap().parse()?,
+        timeout: Duration::from_secs(matches.value_of("timeout").unwrap().parse()?),
+        ..Options::default()
+    };
+    println!("{}", bench::TABLE_HEADER);
+    let mut timings = Vec::new();
+    for puzzle in selected_puzzles(matches)? {
+        let input = puzzle.input(matches.value_of("input"))?;
+        let day_timings =
+            bench::bench(puzzle, input, &options).map_err(|error| invalid_input(puzzle, error))?;
+        println!("{}", day_timings.row());
+        timings.push(day_timings);
+    }
+    if matches.is_present("write") {
+        for file in &["README.md", "runtime.md"] {
+            let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(file);
+            let text = fs::read_to_string(&path)?;
+            fs::write(&path, bench::update_table(&text, &timings))?;
+        }
+    }
+    Ok(())
+}
+
+fn verify(matches: &ArgMatches) -> Result<()> {
+    let timeout = Duration::from_secs(matches.value_of("timeout").unwrap().parse()?);
+    let (mut checked, mut failed) = (0, 0);
+    for puzzle in selected_puzzles(matches)? {
+        let input = puzzle.input(matches.value_of("input"))?;
+        let answers = match (matches.value_of("input"), matches.value_of("answers")) {
+            (Some(_), None) => Answers::default(),
+            (_, answers) => puzzle.answers(answers)?,
+        };
+        let checks = verify::verify(puzzle, input, &answers, timeout)
+            .map_err(|error| invalid_input(puzzle, error))?;
+        for (part, check) in checks {
+            println!("Day {:02} part {}: {}", puzzle.day, part, check);
+            checked += 1;
+            if !check.is_ok() {
+                failed += 1;
+            }
+        }
+    }
+    if failed > 0 {
+        return Err(format!("{} of {} checks failed", failed, checked).into());
+    }
+    Ok(())
+}
+
+fn profiles(matches: &ArgMatches) -> Result<()> {
+    let timeout = Duration::from_secs(matches.value_of("timeout").unwrap().parse()?);
+    let root = Path::new(matches.value_of("dir").unwrap());
+    let profiles = match matches.values_of("profile") {
+        Some(names) => names.map(|name| Profile::new(root, name)).collect(),
+        None => Profile::all(root).map_err(|error| {
+            format!("couldn't read profiles from {}: {}", root.display(), error)
+        })?,
+    };
+    if profiles.is_empty() {
+        return Err(format!("no profiles in {}", root.display()).into());
+    }
+    println!("{}", profile::table_header(&profiles));
+    let mut problems = Vec::new();
+    for puzzle in selected_puzzles(matches)? {
+        let day = profile::run(puzzle, &profiles, timeout)?;
+        println!("{}", day.rows());
+        problems.extend(day.problems());
+    }
+    if !problems.is_empty() {
+        eprintln!();
+        for problem in &problems {
+            eprintln!("{}", problem);
+        }
+        return Err(format!("{} problems found", problems.len()).into());
+    }
+    Ok(())
+}
+
+fn diff(matches: &ArgMatches) -> Result<()> {
+    let seeds = 0..matches.value_of("seeds").unwrap().parse()?;
+    let size = matches.value_of("size").map(str::parse).transpose()?;
+    let mut failures = 0;
+    for puzzle in selected_puzzles(matches)? {
+        for check in differential::find(puzzle.day) {
+            let inputs = differential::inputs(puzzle.day, seeds.clone(), size);
+            match check.run(inputs) {
+                Ok(agreed) => println!(
+                    "Day {:02} {}: agree on {} inputs",
+                    check.day, check.name, agreed
+                ),
+                Err(failure) => {
+                    failures += 1;
+                    println!("Day {:02} {}: {}", check.day, check.name, failure);
+                }
+            }
+        }
+    }
+    if failures > 0 {
+        return Err(format!("{} checks failed", failures).into());
+    }
+    Ok(())
+}
+
+fn generate(matches: &ArgMatches) -> Result<()> {
+    let generator = generate::find(matches.value_of("day").unwrap().parse()?).unwrap();
+    let seed = matches.value_of("seed").unwrap().parse()?;
+    let size = match matches.value_of("size") {
+        Some(size) => size.parse()?,
+        None => generator.default_size,
+    };
+    let input = generator.generate(seed, size);
+    match matches.value_of("output") {
+        Some(output) => fs::write(output, input)?,
+        None => print!("{}", input),
+    }
+    Ok(())
+}
+
+fn render(matches: &ArgMatches) -> Result<()> {
+    let puzzle = registry::find(matches.value_of("day").unwrap().parse()?).unwrap();
+    if !puzzle.can_render() {
+        return Err(format!("day {} has no visualization", puzzle.day).into());
+    }
+    let output = matches.value_of("output").map(Path::new);
+    let format = match matches.value_of("format") {
+        Some(format) => format.parse().unwrap(),
+        None => output
+            .and_then(|output| output.extension()?.to_str()?.parse().ok())
+            .unwrap_or(Format::Gif),
+    };
+    let options = visualize::Options {
+        format,
+        scale: matches.value_of("scale").unwrap().parse()?,
+        delay: match matches.value_of("delay") {
+            Some(delay) => Some(Duration::from_millis(delay.parse()?)),
+            None => None,
+        },
+        frames: match matches.value_of("frames") {
+            Some(frames) => Some(frames.parse()?),
+            None => None,
+        },
+    };
+    let input = puzzle
+        .parse(&puzzle.input(matches.value_of("input"))?)
+        .map_err(|error| invalid_input(puzzle, error))?;
+    let mut out: Box<dyn Write> = match (output, format.extension()) {
+        (Some(output), _) => Box::new(BufWriter::new(File::create(output)?)),
+        (None, Some(extension)) => {
+            let output = format!("day{:02}.{}", puzzle.day, extension);
+            Box::new(BufWriter::new(File::create(output)?))
+        }
+        (None, None) => Box::new(io::stdout()),
+    };
+    puzzle.render(&*input, &mut out, &options).unwrap()?;
+    out.flush()?;
+    Ok(())
+}
+
+fn step(matches: &ArgMatches) -> Result<()> {
+    let puzzle = registry::find(matches.value_of("day").unwrap().parse()?).unwrap();
+    if !puzzle.can_step() {
+        return Err(format!("day {} isn't a simulation", puzzle.day).into());
+    }
+    let delay = match matches.value_of("delay") {
+        Some(delay) => Some(Duration::from_millis(delay.parse()?)),
+        None => None,
+    };
+    let input = puzzle
+        .parse(&puzzle.input(matches.value_of("input"))?)
+        .map_err(|error| invalid_input(puzzle, error))?;
+    puzzle.step(&*input, delay).unwrap()?;
+    Ok(())
+}
+
+fn main() {
+    let matches = app().get_matches();
+    let result = match matches.subcommand() {
+        ("run", Some(matches)) => run(matches),
+        ("params", Some(matches)) => params(matches),
+        ("all", Some(matches)) => all(matches),
+        ("bench", Some(matches)) => bench(matches),
+        ("verify", Some(matches)) => verify(matches),
+        ("profiles", Some(matches)) => profiles(matches),
+        ("diff", Some(matches)) => diff(matches),
+        ("generate", Some(matches)) => generate(matches),
+        ("render", Some(matches)) => render(matches),
+        ("step", Some(matches)) => step(matches),
+        _ => unreachable!(),
+    };
+    if let Err(error) = result {
+        eprintln!("error: {}", error);
+        process::exit(1);
+    }
+}
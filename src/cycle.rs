@@ -0,0 +1,167 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    hash::Hash,
+};
+
+/// Where a deterministic simulation starts repeating itself.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    /// The first step of the repeating part.
+    pub start: usize,
+    /// How many steps it takes to get back to the same state.
+    pub period: usize,
+    /// How far the state moves over one period, as measured by the offset its
+    /// key gives; always 0 for states that stay put.
+    pub drift: i64,
+}
+
+/// Steps `state` until it repeats, comparing states by `key`, and leaves it
+/// one period into the cycle. Never returns if the state doesn't repeat.
+pub fn find<S, K: Hash + Eq>(
+    state: &mut S,
+    mut step: impl FnMut(&mut S),
+    mut key: impl FnMut(&S) -> K,
+) -> Cycle {
+    find_drifting(state, &mut step, |state| (key(state), 0))
+}
+
+/// Like [`find`], for states that can repeat somewhere else. `key` gives a
+/// key that doesn't depend on where the state is, along with its offset.
+pub fn find_drifting<S, K: Hash + Eq>(
+    state: &mut S,
+    step: impl FnMut(&mut S),
+    key: impl FnMut(&S) -> (K, i64),
+) -> Cycle {
+    let (_, cycle) = detect(state, usize::MAX, step, key);
+    cycle.expect("the simulation never repeated")
+}
+
+/// Runs `state` forward to step `target`, skipping whole cycles once it
+/// starts repeating.
+pub fn fast_forward<S, K: Hash + Eq>(
+    state: S,
+    target: usize,
+    step: impl FnMut(&mut S),
+    mut key: impl FnMut(&S) -> K,
+) -> S {
+    fast_forward_drifting(state, target, step, |state| (key(state), 0), |_, _| {})
+}
+
+/// Like [`fast_forward`], for states that can repeat somewhere else. `key`
+/// gives a key that doesn't depend on where the state is, along with its
+/// offset, and `shift` moves a state by the drift of the cycles skipped.
+pub fn fast_forward_drifting<S, K: Hash + Eq>(
+    mut state: S,
+    target: usize,
+    mut step: impl FnMut(&mut S),
+    key: impl FnMut(&S) -> (K, i64),
+    shift: impl FnOnce(&mut S, i64),
+) -> S {
+    let (mut steps, cycle) = detect(&mut state, target, &mut step, key);
+    if let Some(cycle) = cycle {
+        let skipped = (target - steps) / cycle.period;
+        shift(&mut state, skipped as i64 * cycle.drift);
+        steps += skipped * cycle.period;
+    }
+    for _ in steps..target {
+        step(&mut state);
+    }
+    state
+}
+
+/// Steps `state` until it repeats or `limit` steps have been taken, returning
+/// how many steps were taken and the cycle if one was found.
+fn detect<S, K: Hash + Eq>(
+    state: &mut S,
+    limit: usize,
+    mut step: impl FnMut(&mut S),
+    mut key: impl FnMut(&S) -> (K, i64),
+) -> (usize, Option<Cycle>) {
+    let mut seen = HashMap::new();
+    for steps in 0..limit {
+        let (key, offset) = key(state);
+        match seen.entry(key) {
+            Entry::Vacant(entry) => {
+                entry.insert((steps, offset));
+            }
+            Entry::Occupied(entry) => {
+                let (start, start_offset) = *entry.get();
+                let cycle = Cycle {
+                    start,
+                    period: steps - start,
+                    drift: offset - start_offset,
+                };
+                return (steps, Some(cycle));
+            }
+        }
+        step(state);
+    }
+    (limit, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn next(n: &mut u64) {
+        *n = (*n * *n + 1) % 1009;
+    }
+
+    #[test]
+    fn finds_the_cycle() {
+        let mut n = 3;
+        let cycle = find(&mut n, next, |&n| n);
+        let mut naive = 3;
+        for _ in 0..cycle.start {
+            next(&mut naive);
+        }
+        let start = naive;
+        for _ in 0..cycle.period {
+            next(&mut naive);
+        }
+        assert_eq!((naive, cycle.drift), (start, 0));
+        assert_eq!(n, naive);
+    }
+
+    #[test]
+    fn fast_forwards_like_stepping() {
+        let mut naive = 3;
+        for target in 0..200 {
+            assert_eq!(fast_forward(3, target, next, |&n| n), naive);
+            next(&mut naive);
+        }
+    }
+
+    #[test]
+    fn fast_forwards_drifting_states() {
+        // a pattern with three phases that moves two places every time it
+        // goes through them, after a start that doesn't move
+        fn step((phase, pos): &mut (u8, i64)) {
+            if *phase >= 3 {
+                *phase = 0;
+            } else {
+                *phase = (*phase + 1) % 3;
+                if *phase == 0 {
+                    *pos += 2;
+                }
+            }
+        }
+        let key = |&(phase, pos): &(u8, i64)| (phase, pos);
+        let shift = |(_, pos): &mut (u8, i64), by: i64| *pos += by;
+
+        let mut state = (5, 0);
+        let cycle = find_drifting(&mut state, step, key);
+        assert_eq!((cycle.start, cycle.period, cycle.drift), (1, 3, 2));
+
+        let mut naive = (5, 0);
+        for target in 0..100 {
+            assert_eq!(
+                fast_forward_drifting((5, 0), target, step, key, shift),
+                naive
+            );
+            step(&mut naive);
+        }
+        let far = fast_forward_drifting((5, 0), 3_000_000_001, step, key, shift);
+        assert_eq!(far, (0, 2_000_000_000));
+    }
+}
@@ -0,0 +1,207 @@
+use crate::{
+    answer::{Answer, Fact},
+    parse,
+    registry::Part,
+    Solution,
+};
+use std::collections::{HashMap, HashSet};
+
+/// The first frequency reached twice, and the change that reached it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Repeat {
+    pub frequency: i64,
+    /// How many times through the list of changes, counting from 1.
+    pub pass: u64,
+    /// Which change in the list, counting from 0.
+    pub index: usize,
+}
+
+impl Repeat {
+    /// The repeat that comes from the frequency before change `index` on
+    /// pass `pass`, both counting from 0, being `frequency`.
+    fn before(frequency: i64, pass: u64, index: usize, len: usize) -> Self {
+        match index {
+            0 => Repeat {
+                frequency,
+                pass,
+                index: len - 1,
+            },
+            _ => Repeat {
+                frequency,
+                pass: pass + 1,
+                index: index - 1,
+            },
+        }
+    }
+}
+
+impl From<Option<Repeat>> for Answer {
+    fn from(repeat: Option<Repeat>) -> Self {
+        match repeat {
+            Some(repeat) => repeat.frequency.into(),
+            None => Answer::String("never repeats".to_string()),
+        }
+    }
+}
+
+/// Finds the first repeated frequency without going round the list more than
+/// once, in O(n log n).
+///
+/// On pass `k` the frequency before change `i` is `p[i] + k * drift`, where
+/// `p` are the frequencies on the first pass and `drift` is their total
+/// change. So if nothing repeats on the first pass, a frequency only ever
+/// repeats by one of `p` drifting onto another, which must be the same
+/// modulo `drift`. The soonest is between neighbours in one of those groups,
+/// sorted by frequency.
+pub fn first_repeat(changes: &[i32]) -> Option<Repeat> {
+    let len = changes.len();
+    let mut frequencies = Vec::with_capacity(len);
+    let mut seen = HashSet::new();
+    let mut frequency = 0;
+    for (i, &change) in changes.iter().enumerate() {
+        if !seen.insert(frequency) {
+            return Some(Repeat::before(frequency, 0, i, len));
+        }
+        frequencies.push(frequency);
+        frequency += i64::from(change);
+    }
+    let drift = frequency;
+    if len == 0 {
+        return None;
+    }
+    if drift == 0 {
+        return Some(Repeat::before(0, 1, 0, len));
+    }
+
+    let mut groups: HashMap<i64, Vec<(i64, usize)>> = HashMap::new();
+    for (i, &frequency) in frequencies.iter().enumerate() {
+        let group = groups.entry(frequency.rem_euclid(drift)).or_default();
+        group.push((frequency, i));
+    }
+    groups
+        .values_mut()
+        .flat_map(|group| {
+            group.sort_unstable();
+            group
+                .windows(2)
+                .map(|pair| {
+                    let ((low, low_i), (high, high_i)) = (pair[0], pair[1]);
+                    let passes = ((high - low) / drift.abs()) as u64;
+                    // whichever moves towards the other gets there
+                    if drift > 0 {
+                        (passes, low_i, high)
+                    } else {
+                        (passes, high_i, low)
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .min_by_key(|&(passes, i, _)| (passes, i))
+        .map(|(passes, i, frequency)| Repeat::before(frequency, passes, i, len))
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u32 = 1;
+    const INPUT: &'static str = include_str!("input.txt");
+    const ANSWERS: &'static str = include_str!("answers.txt");
+
+    type Input = Vec<i32>;
+    type Part1 = i32;
+    type Part2 = Option<Repeat>;
+
+    fn parse(input: &str) -> parse::Result<Vec<i32>> {
+        parse::lines(input)
+            .map(|mut line| {
+                let change = line.number()?;
+                line.end()?;
+                Ok(change)
+            })
+            .collect()
+    }
+
+    fn part1(frequency_changes: &Vec<i32>) -> i32 {
+        frequency_changes.iter().sum()
+    }
+
+    fn part2(frequency_changes: &Vec<i32>) -> Option<Repeat> {
+        first_repeat(frequency_changes)
+    }
+
+    fn explain(frequency_changes: &Vec<i32>, part: Part) -> Vec<Fact> {
+        match (part, first_repeat(frequency_changes)) {
+            (Part::Two, Some(repeat)) => vec![
+                Fact::new("pass", repeat.pass as i64),
+                Fact::new("change index", repeat.index),
+                Fact::new("change", frequency_changes[repeat.index]),
+            ],
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn changes(s: &str) -> Vec<i32> {
+        Day01::parse(&s.replace(", ", "\n")).unwrap()
+    }
+
+    #[test]
+    fn part1_examples() {
+        assert_eq!(Day01::part1(&changes("+1, -2, +3, +1")), 3);
+        assert_eq!(Day01::part1(&changes("+1, +1, +1")), 3);
+        assert_eq!(Day01::part1(&changes("+1, +1, -2")), 0);
+        assert_eq!(Day01::part1(&changes("-1, -2, -3")), -6);
+    }
+
+    #[test]
+    fn part2_examples() {
+        let frequency = |s| Day01::part2(&changes(s)).unwrap().frequency;
+        assert_eq!(frequency("+1, -2, +3, +1"), 2);
+        assert_eq!(frequency("+1, -1"), 0);
+        assert_eq!(frequency("+3, +3, +4, -2, -4"), 10);
+        assert_eq!(frequency("-6, +3, +8, +5, -6"), 5);
+        assert_eq!(frequency("+7, +7, -2, -7, -4"), 14);
+    }
+
+    #[test]
+    fn repeats_match_going_round() {
+        fn going_round(changes: &[i32]) -> Repeat {
+            let mut seen = HashSet::new();
+            let mut frequency = 0;
+            for pass in 1..=1000 {
+                for (index, &change) in changes.iter().enumerate() {
+                    seen.insert(frequency);
+                    frequency += i64::from(change);
+                    if seen.contains(&frequency) {
+                        return Repeat {
+                            frequency,
+                            pass,
+                            index,
+                        };
+                    }
+                }
+            }
+            panic!("no repeat in 1000 passes");
+        }
+        let mut rng = crate::generate::Rng::new(1);
+        for _ in 0..500 {
+            let len = 1 + rng.below(8);
+            let changes: Vec<i32> = (0..len).map(|_| rng.between(-9, 9) as i32).collect();
+            if let Some(repeat) = first_repeat(&changes) {
+                assert_eq!(repeat, going_round(&changes), "{:?}", changes);
+            }
+        }
+        let repeat = first_repeat(&changes("+1, -2, +3, +1")).unwrap();
+        assert_eq!((repeat.pass, repeat.index), (2, 1));
+    }
+
+    #[test]
+    fn never_repeats() {
+        assert_eq!(first_repeat(&changes("+1, +1")), None);
+        assert_eq!(Answer::from(first_repeat(&[])).to_string(), "never repeats");
+    }
+}
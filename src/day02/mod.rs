@@ -0,0 +1,235 @@
+use crate::{
+    answer::{Answer, Fact},
+    params::{Param, Params},
+    parse,
+    registry::Part,
+    Solution,
+};
+use std::collections::{HashMap, HashSet};
+
+const DISTANCE: Param = Param {
+    name: "distance",
+    doc: "How many letters part 2's box IDs differ by",
+    default: 1,
+    min: 0,
+    max: u64::MAX,
+};
+
+pub struct BoxIds {
+    ids: Vec<String>,
+    distance: usize,
+}
+
+/// Two box IDs that differ by exactly the distance searched for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NearPair {
+    /// Indexes into the IDs, the smaller first.
+    pub first: usize,
+    pub second: usize,
+    /// The letters the IDs have in common, in order.
+    pub common: String,
+}
+
+impl From<Option<NearPair>> for Answer {
+    fn from(pair: Option<NearPair>) -> Self {
+        match pair {
+            Some(pair) => pair.common.into(),
+            None => Answer::String("no near duplicates".to_string()),
+        }
+    }
+}
+
+/// Every pair of `ids` that differ in exactly `k` positions, in order of
+/// their indexes.
+///
+/// Each ID is hashed with every choice of `k` positions masked out, and a
+/// pair of IDs that differ in exactly those positions hash the same for that
+/// choice and no other. That's near-linear in the number of IDs for small
+/// `k`, though the number of choices grows quickly with `k`.
+pub fn near_duplicates(ids: &[String], k: usize) -> Vec<NearPair> {
+    let ids: Vec<Vec<char>> = ids.iter().map(|id| id.chars().collect()).collect();
+    // only IDs of the same length have a Hamming distance
+    let mut by_length: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (i, id) in ids.iter().enumerate() {
+        by_length.entry(id.len()).or_default().push(i);
+    }
+    let mut pairs = Vec::new();
+    for (&len, group) in &by_length {
+        for_each_choice(len, k, |masked| {
+            let mut buckets: HashMap<String, Vec<usize>> = HashMap::new();
+            for &i in group {
+                let key = (0..len)
+                    .filter(|pos| !masked.contains(pos))
+                    .map(|pos| ids[i][pos])
+                    .collect();
+                buckets.entry(key).or_default().push(i);
+            }
+            for (common, bucket) in buckets {
+                for (n, &first) in bucket.iter().enumerate() {
+                    for &second in &bucket[n + 1..] {
+                        if masked
+                            .iter()
+                            .all(|&pos| ids[first][pos] != ids[second][pos])
+                        {
+                            pairs.push(NearPair {
+                                first,
+                                second,
+                                common: common.clone(),
+                            });
+                        }
+                    }
+                }
+            }
+        });
+    }
+    pairs.sort_by_key(|pair| (pair.first, pair.second));
+    pairs
+}
+
+/// Calls `f` with every way of choosing `k` of the numbers below `n`, in
+/// increasing order.
+fn for_each_choice(n: usize, k: usize, mut f: impl FnMut(&[usize])) {
+    if k > n {
+        return;
+    }
+    let mut chosen: Vec<usize> = (0..k).collect();
+    loop {
+        f(&chosen);
+        // move the last number that can move up by one, and pack the rest
+        // in after it
+        match (0..k).rev().find(|&i| chosen[i] < n - k + i) {
+            Some(i) => {
+                chosen[i] += 1;
+                for j in i + 1..k {
+                    chosen[j] = chosen[j - 1] + 1;
+                }
+            }
+            None => return,
+        }
+    }
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u32 = 2;
+    const INPUT: &'static str = include_str!("input.txt");
+    const ANSWERS: &'static str = include_str!("answers.txt");
+
+    const PARAMS: &'static [Param] = &[DISTANCE];
+
+    type Input = BoxIds;
+    type Part1 = usize;
+    type Part2 = Option<NearPair>;
+
+    fn parse(input: &str) -> parse::Result<BoxIds> {
+        Ok(BoxIds {
+            ids: input.lines().map(String::from).collect(),
+            distance: DISTANCE.default as usize,
+        })
+    }
+
+    fn configure(box_ids: &mut BoxIds, params: &Params) {
+        box_ids.distance = params.get(&DISTANCE) as usize;
+    }
+
+    fn part1(box_ids: &BoxIds) -> usize {
+        let mut twos = 0;
+        let mut threes = 0;
+        for id in &box_ids.ids {
+            let counts = letter_counts(id);
+            if counts.contains(&2) {
+                twos += 1;
+            }
+            if counts.contains(&3) {
+                threes += 1;
+            }
+        }
+        return twos * threes;
+
+        fn letter_counts(id: &str) -> HashSet<u8> {
+            let mut map = HashMap::new();
+            for c in id.chars() {
+                *map.entry(c).or_insert(0) += 1;
+            }
+            map.drain().map(|(_, count)| count).collect()
+        }
+    }
+
+    fn part2(box_ids: &BoxIds) -> Option<NearPair> {
+        near_duplicates(&box_ids.ids, box_ids.distance)
+            .into_iter()
+            .next()
+    }
+
+    fn explain(box_ids: &BoxIds, part: Part) -> Vec<Fact> {
+        if part == Part::One {
+            return Vec::new();
+        }
+        near_duplicates(&box_ids.ids, box_ids.distance)
+            .into_iter()
+            .map(|pair| {
+                let ids = format!(
+                    "{} and {}",
+                    box_ids.ids[pair.first], box_ids.ids[pair.second]
+                );
+                Fact::new(ids, pair.common)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        let ids = Day02::parse("abcdef\nbababc\nabbcde\nabcccd\naabcdd\nabcdee\nababab").unwrap();
+        assert_eq!(Day02::part1(&ids), 12);
+    }
+
+    #[test]
+    fn part2_example() {
+        let ids = Day02::parse("abcde\nfghij\nklmno\npqrst\nfguij\naxcye\nwvxyz").unwrap();
+        assert_eq!(Day02::part2(&ids).unwrap().common, "fgij");
+    }
+
+    #[test]
+    fn no_near_duplicates() {
+        let mut ids = Day02::parse("abcde\nfghij\nklmno").unwrap();
+        assert_eq!(Day02::part2(&ids), None);
+        ids.distance = 0;
+        assert_eq!(Day02::part2(&ids), None);
+        assert_eq!(
+            Answer::from(Day02::part2(&ids)).to_string(),
+            "no near duplicates"
+        );
+    }
+
+    #[test]
+    fn near_duplicates_at_any_distance() {
+        let ids: Vec<String> = ["abcde", "abxde", "abxdy", "zbxde", "abcd", "abcde"]
+            .iter()
+            .map(|id| id.to_string())
+            .collect();
+        let pairs = |k| -> Vec<_> {
+            near_duplicates(&ids, k)
+                .into_iter()
+                .map(|pair| (pair.first, pair.second, pair.common))
+                .collect()
+        };
+        assert_eq!(pairs(0), [(0, 5, "abcde".to_string())]);
+        assert_eq!(
+            pairs(1),
+            [
+                (0, 1, "abde".to_string()),
+                (1, 2, "abxd".to_string()),
+                (1, 3, "bxde".to_string()),
+                (1, 5, "abde".to_string()),
+            ]
+        );
+        assert_eq!(pairs(2).len(), 5);
+        assert_eq!(pairs(6), []);
+    }
+}
@@ -0,0 +1,209 @@
+use super::{
+    sweep::{cells_overlap, sweep},
+    Claim,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::Range,
+};
+
+/// The most claims on top of each other anywhere on the fabric.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Stack {
+    pub depth: u32,
+    /// The leftmost, then topmost, rectangle stacked that deep. Other
+    /// rectangles may be too.
+    pub x: Range<u64>,
+    pub y: Range<u64>,
+    /// The IDs of the claims in the stack, in the order given.
+    pub claims: Vec<u32>,
+}
+
+/// Which claims overlap which, and how deeply they stack up.
+#[derive(Clone, Debug)]
+pub struct OverlapGraph {
+    ids: Vec<u32>,
+    indexes: HashMap<u32, usize>,
+    /// The claims each claim overlaps, by index, in order.
+    neighbours: Vec<Vec<usize>>,
+    deepest: Option<Stack>,
+    area_by_count: BTreeMap<u32, u64>,
+}
+
+impl OverlapGraph {
+    pub fn new(claims: &[Claim]) -> Self {
+        let mut neighbours = vec![Vec::new(); claims.len()];
+        let mut deepest: Option<Stack> = None;
+        let mut area_by_count = BTreeMap::new();
+        sweep(claims, |strip| {
+            // a claim starting here overlaps every claim crossing the strip
+            // whose cells it shares
+            for &i in strip.entered {
+                for &j in strip.active {
+                    if i != j && cells_overlap(&strip.cells[i], &strip.cells[j]) {
+                        neighbours[i].push(j);
+                        neighbours[j].push(i);
+                    }
+                }
+            }
+
+            let width = strip.x.end - strip.x.start;
+            for (cell, &count) in strip.counts.iter().enumerate() {
+                if count == 0 {
+                    continue;
+                }
+                *area_by_count.entry(count).or_insert(0) += width * strip.height(cell);
+                if deepest.as_ref().is_none_or(|stack| count > stack.depth) {
+                    let mut stack: Vec<usize> = strip
+                        .active
+                        .iter()
+                        .copied()
+                        .filter(|&i| strip.cells[i].contains(&cell))
+                        .collect();
+                    stack.sort_unstable();
+                    deepest = Some(Stack {
+                        depth: count,
+                        x: strip.x.clone(),
+                        y: strip.ys[cell]..strip.ys[cell + 1],
+                        claims: stack.into_iter().map(|i| claims[i].id).collect(),
+                    });
+                }
+            }
+        });
+        for claims in &mut neighbours {
+            // claims starting at the same strip find each other twice
+            claims.sort_unstable();
+            claims.dedup();
+        }
+
+        let ids: Vec<u32> = claims.iter().map(|claim| claim.id).collect();
+        let indexes = ids.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+        OverlapGraph {
+            ids,
+            indexes,
+            neighbours,
+            deepest,
+            area_by_count,
+        }
+    }
+
+    /// The IDs of the claims that claim `id` overlaps, in the order given,
+    /// or `None` if there's no such claim.
+    pub fn overlapping(&self, id: u32) -> Option<Vec<u32>> {
+        let i = *self.indexes.get(&id)?;
+        Some(self.neighbours[i].iter().map(|&j| self.ids[j]).collect())
+    }
+
+    /// The groups of claims connected by overlaps, each in the order given
+    /// and ordered by their first claim. A claim that overlaps nothing is a
+    /// group of its own.
+    pub fn components(&self) -> Vec<Vec<u32>> {
+        let mut component = vec![None; self.ids.len()];
+        let mut components = Vec::new();
+        for start in 0..self.ids.len() {
+            if component[start].is_some() {
+                continue;
+            }
+            let n = components.len();
+            let mut members = vec![start];
+            component[start] = Some(n);
+            let mut unvisited = vec![start];
+            while let Some(i) = unvisited.pop() {
+                for &j in &self.neighbours[i] {
+                    if component[j].is_none() {
+                        component[j] = Some(n);
+                        members.push(j);
+                        unvisited.push(j);
+                    }
+                }
+            }
+            members.sort_unstable();
+            components.push(members.into_iter().map(|i| self.ids[i]).collect());
+        }
+        components
+    }
+
+    /// The deepest stack of claims, or `None` if no claim covers anything.
+    pub fn deepest(&self) -> Option<&Stack> {
+        self.deepest.as_ref()
+    }
+
+    /// How many square inches are covered by each number of claims, from
+    /// one up.
+    pub fn area_by_count(&self) -> &BTreeMap<u32, u64> {
+        &self.area_by_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{day03::Day03, Solution};
+
+    #[test]
+    fn example_graph() {
+        let claims = Day03::parse(
+            "#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n#4 @ 2,2: 2x3\n#5 @ 6,6: 0x3",
+        )
+        .unwrap();
+        let graph = OverlapGraph::new(&claims);
+        assert_eq!(graph.overlapping(1), Some(vec![2, 4]));
+        assert_eq!(graph.overlapping(2), Some(vec![1, 4]));
+        assert_eq!(graph.overlapping(3), Some(vec![]));
+        assert_eq!(graph.overlapping(5), Some(vec![]));
+        assert_eq!(graph.overlapping(6), None);
+        assert_eq!(graph.components(), [vec![1, 2, 4], vec![3], vec![5]]);
+        assert_eq!(
+            graph.deepest(),
+            Some(&Stack {
+                depth: 3,
+                x: 3..4,
+                y: 3..5,
+                claims: vec![1, 2, 4],
+            })
+        );
+        let area: Vec<_> = graph
+            .area_by_count()
+            .iter()
+            .map(|(&n, &a)| (n, a))
+            .collect();
+        assert_eq!(area, [(1, 26), (2, 5), (3, 2)]);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let input = crate::generate::find(3).unwrap().generate(5, 200);
+        let claims = Day03::parse(&input).unwrap();
+        let graph = OverlapGraph::new(&claims);
+
+        let overlap = |a: &Claim, b: &Claim| {
+            a.x.max(b.x) < (a.x + a.width).min(b.x + b.width)
+                && a.y.max(b.y) < (a.y + a.height).min(b.y + b.height)
+        };
+        for a in &claims {
+            let expected: Vec<u32> = claims
+                .iter()
+                .filter(|b| b.id != a.id && overlap(a, b))
+                .map(|b| b.id)
+                .collect();
+            assert_eq!(graph.overlapping(a.id), Some(expected));
+        }
+
+        let mut counts = HashMap::new();
+        for claim in &claims {
+            for x in claim.x..claim.x + claim.width {
+                for y in claim.y..claim.y + claim.height {
+                    *counts.entry((x, y)).or_insert(0) += 1;
+                }
+            }
+        }
+        let mut area_by_count = BTreeMap::new();
+        for &count in counts.values() {
+            *area_by_count.entry(count).or_insert(0) += 1;
+        }
+        assert_eq!(graph.area_by_count(), &area_by_count);
+        let depth = *counts.values().max().unwrap();
+        assert_eq!(graph.deepest().unwrap().depth, depth);
+        assert_eq!(graph.deepest().unwrap().claims.len() as u32, depth);
+    }
+}
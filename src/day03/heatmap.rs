@@ -0,0 +1,261 @@
+use super::{sweep, Claim};
+use crate::{
+    grid::{Grid, Pos},
+    visualize::{Frame, Rgb, Simulation},
+};
+use std::ops::Range;
+
+/// How many claims deep the heatmap tells apart. Anything deeper is drawn
+/// like the deepest.
+const LEVELS: usize = 8;
+const OUTLINE: u8 = LEVELS as u8 + 1;
+const MARGIN: u8 = LEVELS as u8 + 2;
+const TEXT: u8 = LEVELS as u8 + 3;
+/// The most cells along either side of the fabric. A bigger one is drawn
+/// with a cell for every few inches.
+const MAX_SIDE: usize = 2000;
+
+/// Unclaimed fabric, then one to `LEVELS` claims, then the outline, the
+/// space around the fabric and the legend's labels.
+pub(super) const PALETTE: &[Rgb] = &[
+    Rgb(0x28, 0x28, 0x28),
+    Rgb(0x32, 0x0a, 0x5e),
+    Rgb(0x6a, 0x17, 0x6e),
+    Rgb(0x9f, 0x2a, 0x63),
+    Rgb(0xd4, 0x48, 0x42),
+    Rgb(0xf5, 0x7d, 0x15),
+    Rgb(0xfa, 0xc2, 0x28),
+    Rgb(0xf6, 0xe0, 0x5a),
+    Rgb(0xfc, 0xff, 0xa4),
+    Rgb(0x00, 0xff, 0x80),
+    Rgb(0, 0, 0),
+    Rgb(0xff, 0xff, 0xff),
+];
+
+/// The fabric with the claims laid on it one at a time, in ID order.
+#[derive(Clone)]
+pub struct Fabric {
+    claims: Vec<Claim>,
+    /// How many of the claims have been laid so far.
+    laid: usize,
+    /// How many inches along each side a cell is.
+    scale: usize,
+    /// How many of them cover the middle square inch of each cell, up to
+    /// 255.
+    counts: Grid<u8>,
+    /// The claim part 2 finds, outlined once every claim has been laid.
+    intact: Option<Claim>,
+}
+
+impl Fabric {
+    pub fn new(claims: &[Claim]) -> Self {
+        let intact = sweep::overlaps(claims)
+            .intact
+            .first()
+            .and_then(|&id| claims.iter().find(|claim| claim.id == id))
+            .cloned();
+        let mut claims = claims.to_vec();
+        claims.sort_by_key(|claim| claim.id);
+        let width = claims.iter().map(|c| c.xs().end).max().unwrap_or(0);
+        let height = claims.iter().map(|c| c.ys().end).max().unwrap_or(0);
+        let scale = width.max(height).div_ceil(MAX_SIDE).max(1);
+        Fabric {
+            claims,
+            laid: 0,
+            scale,
+            counts: Grid::new(width.div_ceil(scale), height.div_ceil(scale), 0),
+            intact,
+        }
+    }
+
+    /// The cells whose middle square inch is one of `inches`.
+    fn covered(&self, inches: Range<usize>) -> Range<usize> {
+        let first = |inch: usize| inch.saturating_sub(self.scale / 2).div_ceil(self.scale);
+        first(inches.start)..first(inches.end)
+    }
+
+    /// The cells any of `inches` are in, or `None` if there are none.
+    fn touched(&self, inches: Range<usize>) -> Option<Range<usize>> {
+        if inches.is_empty() {
+            return None;
+        }
+        Some(inches.start / self.scale..(inches.end - 1) / self.scale + 1)
+    }
+}
+
+impl Claim {
+    fn xs(&self) -> Range<usize> {
+        self.x as usize..self.x as usize + self.width as usize
+    }
+
+    fn ys(&self) -> Range<usize> {
+        self.y as usize..self.y as usize + self.height as usize
+    }
+}
+
+fn fill(frame: &mut Frame, xs: Range<usize>, ys: Range<usize>, value: u8) {
+    for y in ys {
+        for x in xs.clone() {
+            frame[(x, y)] = value;
+        }
+    }
+}
+
+/// A 3×5 font, just enough for the legend.
+fn glyph(c: char) -> [&'static str; 5] {
+    match c {
+        '0' => ["###", "#.#", "#.#", "#.#", "###"],
+        '1' => [".#.", "##.", ".#.", ".#.", "###"],
+        '2' => ["###", "..#", "###", "#..", "###"],
+        '3' => ["###", "..#", ".##", "..#", "###"],
+        '4' => ["#.#", "#.#", "###", "..#", "..#"],
+        '5' => ["###", "#..", "###", "..#", "###"],
+        '6' => ["###", "#..", "###", "#.#", "###"],
+        '7' => ["###", "..#", "..#", ".#.", ".#."],
+        '8' => ["###", "#.#", "###", "#.#", "###"],
+        '9' => ["###", "#.#", "###", "..#", "###"],
+        '+' => ["...", ".#.", "###", ".#.", "..."],
+        _ => panic!("no glyph for `{}`", c),
+    }
+}
+
+/// Writes `text` with its top left corner at `(x, y)`, each pixel of the
+/// font `unit` cells across.
+fn write_text(frame: &mut Frame, text: &str, (x, y): Pos, unit: usize) {
+    for (i, c) in text.chars().enumerate() {
+        for (row, line) in glyph(c).iter().enumerate() {
+            for (column, _) in line.match_indices('#') {
+                let left = x + (4 * i + column) * unit;
+                let top = y + row * unit;
+                fill(frame, left..left + unit, top..top + unit, TEXT);
+            }
+        }
+    }
+}
+
+impl Simulation for Fabric {
+    fn step(&mut self) -> bool {
+        let (xs, ys) = match self.claims.get(self.laid) {
+            Some(claim) => (self.covered(claim.xs()), self.covered(claim.ys())),
+            None => return false,
+        };
+        for y in ys {
+            for x in xs.clone() {
+                let count = &mut self.counts[(x, y)];
+                *count = count.saturating_add(1);
+            }
+        }
+        self.laid += 1;
+        true
+    }
+
+    /// The fabric with a legend underneath: a swatch of each level's color
+    /// above how many claims it stands for.
+    fn frame(&self) -> Frame {
+        let unit = (self.counts.width() / 200).max(1);
+        let swatch = (self.counts.width() / LEVELS).max(8 * unit);
+        let width = self.counts.width().max(LEVELS * swatch);
+        let top = self.counts.height();
+        let mut frame = Grid::new(width, top + 12 * unit, MARGIN);
+        for (pos, &count) in self.counts.iter() {
+            frame[pos] = count.min(LEVELS as u8);
+        }
+
+        if self.laid == self.claims.len() {
+            // a claim with no area has nothing to outline
+            let cells = self
+                .intact
+                .as_ref()
+                .and_then(|claim| Some((self.touched(claim.xs())?, self.touched(claim.ys())?)));
+            if let Some((xs, ys)) = cells {
+                fill(&mut frame, xs.clone(), ys.start..ys.start + 1, OUTLINE);
+                fill(&mut frame, xs.clone(), ys.end - 1..ys.end, OUTLINE);
+                fill(&mut frame, xs.start..xs.start + 1, ys.clone(), OUTLINE);
+                fill(&mut frame, xs.end - 1..xs.end, ys, OUTLINE);
+            }
+        }
+
+        for level in 1..=LEVELS {
+            let left = (level - 1) * swatch;
+            let swatch_top = top + unit;
+            fill(
+                &mut frame,
+                left..left + swatch,
+                swatch_top..swatch_top + 4 * unit,
+                level as u8,
+            );
+            let label = match level {
+                LEVELS => format!("{}+", level),
+                _ => level.to_string(),
+            };
+            let text_width = 4 * unit * label.len() - unit;
+            let text_left = left + (swatch - text_width) / 2;
+            write_text(&mut frame, &label, (text_left, top + 6 * unit), unit);
+        }
+        frame
+    }
+
+    fn status(&self) -> String {
+        match self.laid {
+            0 => format!("0 of {} claims", self.claims.len()),
+            laid => format!(
+                "{} of {} claims, last #{}",
+                laid,
+                self.claims.len(),
+                self.claims[laid - 1].id
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{day03::Day03, visualize, Solution};
+
+    #[test]
+    fn example_heatmap() {
+        let claims = Day03::parse("#3 @ 5,5: 2x2\n#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4").unwrap();
+        let frames: Vec<_> = visualize::frames(Fabric::new(&claims)).collect();
+        assert_eq!(frames.len(), 4);
+
+        // the fabric is 7 wide, which is too narrow for the legend
+        let (first, last) = (&frames[0], &frames[3]);
+        assert_eq!((last.width(), last.height()), (64, 19));
+        assert_eq!(first[(1, 3)], 0);
+        assert_eq!(frames[1][(1, 3)], 1);
+        assert_eq!(frames[1][(5, 5)], 0);
+        assert_eq!(last[(3, 3)], 2);
+        assert_eq!(last[(2, 4)], 1);
+        assert_eq!(last[(7, 0)], MARGIN);
+        let overlap = last.iter().filter(|&((x, y), &c)| x < 7 && y < 7 && c == 2);
+        assert_eq!(overlap.count(), Day03::part1(&claims));
+
+        // claim 3 is outlined, and being 2x2 it's all outline
+        let outline: Vec<_> = last.iter().filter(|&(_, &c)| c == OUTLINE).collect();
+        assert_eq!(outline.len(), 4);
+        assert!(frames[2].cells().all(|&c| c != OUTLINE));
+
+        // the swatches, then `1` written under the first
+        assert_eq!(last[(0, 8)], 1);
+        assert_eq!(last[(63, 11)], LEVELS as u8);
+        assert_eq!(last[(2, 13)], MARGIN);
+        assert_eq!(last[(3, 13)], TEXT);
+    }
+
+    #[test]
+    fn huge_fabric() {
+        let claims = Day03::parse("#1 @ 0,0: 4000000000x10\n#2 @ 5,5: 2x2").unwrap();
+        let fabric = Fabric::new(&claims);
+        assert_eq!(fabric.scale, 2_000_000);
+        let last = visualize::frames(fabric).last().unwrap();
+        assert_eq!((last.width(), last.height()), (2000, 1 + 12 * 10));
+    }
+
+    #[test]
+    fn empty_intact_claim() {
+        let claims = Day03::parse("#1 @ 5,5: 0x3\n#2 @ 1,1: 2x2\n#3 @ 1,1: 1x1").unwrap();
+        let last = visualize::frames(Fabric::new(&claims)).last().unwrap();
+        assert!(last.cells().all(|&c| c != OUTLINE));
+    }
+}
@@ -0,0 +1,174 @@
+use crate::{
+    answer::{Answer, Fact},
+    parse::{self, Line},
+    registry::Part,
+    visualize::{self, Frame, Style},
+    Render, Simulate, Solution,
+};
+use graph::OverlapGraph;
+use heatmap::Fabric;
+use std::time::Duration;
+
+pub mod graph;
+pub mod heatmap;
+pub(crate) mod reference;
+pub mod sweep;
+
+#[derive(Clone)]
+pub struct Claim {
+    id: u32,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+impl Claim {
+    /// Parses a line like `#1 @ 1,3: 4x4`.
+    fn parse(mut line: Line) -> parse::Result<Self> {
+        line.literal("#")?;
+        let id = line.number()?;
+        line.literal("@")?;
+        let x = line.number()?;
+        line.literal(",")?;
+        let y = line.number()?;
+        line.literal(":")?;
+        let width = line.number()?;
+        line.literal("x")?;
+        let height = line.number()?;
+        line.end()?;
+        Ok(Claim {
+            id,
+            x,
+            y,
+            width,
+            height,
+        })
+    }
+}
+
+/// The ID of the first claim that overlaps no other, if any claim doesn't.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Intact(pub Option<u32>);
+
+impl From<Intact> for Answer {
+    fn from(intact: Intact) -> Self {
+        match intact.0 {
+            Some(id) => id.into(),
+            None => Answer::String("no intact claim".to_string()),
+        }
+    }
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u32 = 3;
+    const INPUT: &'static str = include_str!("input.txt");
+    const ANSWERS: &'static str = include_str!("answers.txt");
+
+    type Input = Vec<Claim>;
+    type Part1 = usize;
+    type Part2 = Intact;
+
+    fn parse(input: &str) -> parse::Result<Vec<Claim>> {
+        parse::lines(input).map(Claim::parse).collect()
+    }
+
+    fn part1(claims: &Vec<Claim>) -> usize {
+        sweep::overlaps(claims).area as usize
+    }
+
+    fn part2(claims: &Vec<Claim>) -> Intact {
+        Intact(sweep::overlaps(claims).intact.first().copied())
+    }
+
+    fn explain(claims: &Vec<Claim>, part: Part) -> Vec<Fact> {
+        let graph = OverlapGraph::new(claims);
+        match part {
+            Part::One => {
+                let mut facts: Vec<_> = graph
+                    .area_by_count()
+                    .iter()
+                    .map(|(count, &area)| {
+                        Fact::new(format!("square inches under {} claims", count), area as i64)
+                    })
+                    .collect();
+                if let Some(stack) = graph.deepest() {
+                    let corner = vec![stack.x.start as i64, stack.y.start as i64];
+                    facts.push(Fact::new("deepest stack", stack.depth));
+                    facts.push(Fact::new("deepest at", Answer::Coord(corner)));
+                }
+                facts
+            }
+            Part::Two => {
+                let components = graph.components();
+                let largest = components.iter().map(Vec::len).max().unwrap_or(0);
+                vec![
+                    Fact::new("overlapping groups", components.len()),
+                    Fact::new("largest group", largest),
+                ]
+            }
+        }
+    }
+}
+
+impl Render for Day03 {
+    const STYLE: Style = Style {
+        palette: heatmap::PALETTE,
+        delay: Duration::from_millis(20),
+    };
+
+    /// The bare fabric, then after each claim is laid on it in ID order.
+    fn frames(claims: &Vec<Claim>) -> Box<dyn Iterator<Item = Frame> + '_> {
+        Box::new(visualize::frames(Self::simulation(claims)))
+    }
+}
+
+impl Simulate for Day03 {
+    type Simulation = Fabric;
+
+    fn simulation(claims: &Vec<Claim>) -> Fabric {
+        Fabric::new(claims)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day03::part1(&Day03::parse(EXAMPLE).unwrap()), 4);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            Day03::part2(&Day03::parse(EXAMPLE).unwrap()),
+            Intact(Some(3))
+        );
+    }
+
+    #[test]
+    fn no_intact_claim() {
+        let claims = Day03::parse("#1 @ 1,3: 4x4\n#2 @ 1,3: 4x4").unwrap();
+        assert_eq!(Day03::part2(&claims), Intact(None));
+        assert_eq!(reference::part2(&claims), Intact(None));
+        assert_eq!(
+            Answer::from(Day03::part2(&claims)).to_string(),
+            "no intact claim"
+        );
+    }
+
+    #[test]
+    fn malformed_claim() {
+        let error = Day03::parse("#1 @ 1,3: 4x4\n#2 @ 3;1: 4x4").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 7: expected `,`, found `;`"
+        );
+    }
+}
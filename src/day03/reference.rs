@@ -0,0 +1,34 @@
+//! The first, straightforward solution, kept to check the faster one against.
+
+use super::{Claim, Intact};
+use itertools::iproduct;
+use std::collections::HashMap;
+
+/// How many claims cover each square inch that any claim covers.
+fn fabric_claimed(claims: &[Claim]) -> HashMap<(u32, u32), u32> {
+    let mut map = HashMap::new();
+    for claim in claims {
+        for x in claim.x..claim.x + claim.width {
+            for y in claim.y..claim.y + claim.height {
+                *map.entry((x, y)).or_insert(0) += 1;
+            }
+        }
+    }
+    map
+}
+
+pub(crate) fn part1(claims: &[Claim]) -> usize {
+    fabric_claimed(claims).values().filter(|&&v| v > 1).count()
+}
+
+pub(crate) fn part2(claims: &[Claim]) -> Intact {
+    let fabric_claimed = fabric_claimed(claims);
+    let claim = claims.iter().find(|claim| {
+        iproduct!(
+            claim.x..claim.x + claim.width,
+            claim.y..claim.y + claim.height
+        )
+        .all(|x| fabric_claimed[&x] == 1)
+    });
+    Intact(claim.map(|claim| claim.id))
+}
@@ -0,0 +1,182 @@
+use super::Claim;
+use std::ops::Range;
+
+/// How the claims overlap.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Overlaps {
+    /// How many square inches are in two or more claims.
+    pub area: u64,
+    /// The IDs of the claims that overlap no other, in the order given.
+    pub intact: Vec<u32>,
+}
+
+/// The distinct values of `edges`, in order.
+fn compress(edges: impl Iterator<Item = u64>) -> Vec<u64> {
+    let mut edges: Vec<_> = edges.collect();
+    edges.sort_unstable();
+    edges.dedup();
+    edges
+}
+
+/// One strip of the fabric, between neighbouring left and right edges of
+/// claims, with the counts of how many claims cover each cell of it.
+pub(super) struct Strip<'a> {
+    pub x: Range<u64>,
+    /// The claims that start at this strip, by index.
+    pub entered: &'a [usize],
+    /// Every claim crossing this strip, by index.
+    pub active: &'a [usize],
+    /// How many claims cover each cell of the strip.
+    pub counts: &'a [u32],
+    /// Where the cells start and end.
+    pub ys: &'a [u64],
+    /// The cells each claim covers, by index.
+    pub cells: &'a [Range<usize>],
+}
+
+impl Strip<'_> {
+    pub fn height(&self, cell: usize) -> u64 {
+        self.ys[cell + 1] - self.ys[cell]
+    }
+}
+
+/// Whether two claims' cells overlap, given that they cross the same strip.
+pub(super) fn cells_overlap(a: &Range<usize>, b: &Range<usize>) -> bool {
+    a.start.max(b.start) < a.end.min(b.end)
+}
+
+/// Visits every strip of the fabric from left to right, so that the cost
+/// depends on how many claims there are and not how big they are.
+///
+/// The fabric is cut into strips at every claim's left and right edges, and
+/// each strip into cells at every top and bottom edge. A line sweeps across
+/// the strips, keeping count of how many claims cover each cell of the strip
+/// it's on. That's O(n²) time for n claims, but only O(n) memory.
+pub(super) fn sweep(claims: &[Claim], mut visit: impl FnMut(&Strip)) {
+    let left = |c: &Claim| u64::from(c.x);
+    let right = |c: &Claim| u64::from(c.x) + u64::from(c.width);
+    let top = |c: &Claim| u64::from(c.y);
+    let bottom = |c: &Claim| u64::from(c.y) + u64::from(c.height);
+    let xs = compress(claims.iter().flat_map(|c| [left(c), right(c)]));
+    let ys = compress(claims.iter().flat_map(|c| [top(c), bottom(c)]));
+    let cell = |y| ys.binary_search(&y).unwrap();
+    let cells: Vec<Range<usize>> = claims
+        .iter()
+        .map(|c| cell(top(c))..cell(bottom(c)))
+        .collect();
+
+    let mut by_left: Vec<usize> = (0..claims.len()).collect();
+    by_left.sort_by_key(|&i| left(&claims[i]));
+    let mut by_left = by_left.into_iter().peekable();
+
+    let mut active: Vec<usize> = Vec::new();
+    let mut entered = Vec::new();
+    let mut counts = vec![0u32; ys.len().saturating_sub(1)];
+    for strip in xs.windows(2) {
+        let x = strip[0]..strip[1];
+        active.retain(|&i| {
+            let ended = right(&claims[i]) <= x.start;
+            if ended {
+                for count in &mut counts[cells[i].clone()] {
+                    *count -= 1;
+                }
+            }
+            !ended
+        });
+        entered.clear();
+        while let Some(i) = by_left.next_if(|&i| left(&claims[i]) == x.start) {
+            // a claim with no width covers nothing
+            if right(&claims[i]) > x.start {
+                for count in &mut counts[cells[i].clone()] {
+                    *count += 1;
+                }
+                entered.push(i);
+            }
+        }
+        active.extend(&entered);
+        visit(&Strip {
+            x,
+            entered: &entered,
+            active: &active,
+            counts: &counts,
+            ys: &ys,
+            cells: &cells,
+        });
+    }
+}
+
+/// Works out the overlapping area and the intact claims.
+pub fn overlaps(claims: &[Claim]) -> Overlaps {
+    // how many of the cells before each one are covered more than once
+    let mut crowded_before = Vec::new();
+    let mut overlapped = vec![false; claims.len()];
+    let mut area = 0;
+    sweep(claims, |strip| {
+        crowded_before.clear();
+        crowded_before.push(0);
+        let mut crowded_height = 0;
+        for (i, &count) in strip.counts.iter().enumerate() {
+            let crowded = count > 1;
+            crowded_before.push(crowded_before[i] + crowded as usize);
+            if crowded {
+                crowded_height += strip.height(i);
+            }
+        }
+        area += (strip.x.end - strip.x.start) * crowded_height;
+        if crowded_height > 0 {
+            for &i in strip.active {
+                let cells = &strip.cells[i];
+                if crowded_before[cells.end] > crowded_before[cells.start] {
+                    overlapped[i] = true;
+                }
+            }
+        }
+    });
+
+    let intact = claims
+        .iter()
+        .zip(overlapped)
+        .filter(|&(_, overlapped)| !overlapped)
+        .map(|(claim, _)| claim.id)
+        .collect();
+    Overlaps { area, intact }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn huge_claims() {
+        let claims = [
+            Claim {
+                id: 1,
+                x: 0,
+                y: 0,
+                width: 4_000_000_000,
+                height: 4_000_000_000,
+            },
+            Claim {
+                id: 2,
+                x: 3_000_000_000,
+                y: 3_000_000_000,
+                width: 2_000_000_000,
+                height: 4_000_000_000,
+            },
+            Claim {
+                id: 3,
+                x: 4_000_000_000,
+                y: 0,
+                width: 10,
+                height: 10,
+            },
+        ];
+        assert_eq!(
+            overlaps(&claims),
+            Overlaps {
+                area: 1_000_000_000 * 1_000_000_000,
+                intact: vec![3],
+            }
+        );
+    }
+}
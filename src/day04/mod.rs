@@ -0,0 +1,252 @@
+use crate::{
+    answer::{Answer, Fact},
+    parse::{self, Line},
+    registry::Part,
+    Solution,
+};
+use std::{cmp::Reverse, collections::HashMap};
+
+enum Event {
+    BeginsShift(u32),
+    FallsAsleep,
+    WakesUp,
+}
+
+struct Record<'a> {
+    /// Year, month, day, hour and minute, for sorting.
+    time: (u32, u32, u32, u32, u32),
+    event: Event,
+    /// The line positioned at the event, for reporting events out of order.
+    at: Line<'a>,
+}
+
+impl<'a> Record<'a> {
+    /// Parses a line like `[1518-11-01 00:05] falls asleep`.
+    fn parse(mut line: Line<'a>) -> parse::Result<Self> {
+        line.literal("[")?;
+        let year = line.number()?;
+        line.literal("-")?;
+        let month = line.number()?;
+        line.literal("-")?;
+        let day = line.number()?;
+        let hour = line.number()?;
+        line.literal(":")?;
+        let minute = line.number()?;
+        line.literal("]")?;
+        line.skip_spaces();
+        let at = line;
+        let event = match line.word() {
+            "Guard" => {
+                line.literal("#")?;
+                let id = line.number()?;
+                line.literal("begins shift")?;
+                Event::BeginsShift(id)
+            }
+            "falls" => {
+                line.literal("asleep")?;
+                Event::FallsAsleep
+            }
+            "wakes" => {
+                line.literal("up")?;
+                Event::WakesUp
+            }
+            _ => return Err(at.error("`Guard`, `falls` or `wakes`")),
+        };
+        line.end()?;
+        Ok(Record {
+            time: (year, month, day, hour, minute),
+            event,
+            at,
+        })
+    }
+
+    fn minute(&self) -> usize {
+        self.time.4 as usize
+    }
+}
+
+/// A guard's ID times a minute, unless no guard ever falls asleep.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Asleep(pub Option<u32>);
+
+impl From<Asleep> for Answer {
+    fn from(asleep: Asleep) -> Self {
+        match asleep.0 {
+            Some(product) => product.into(),
+            None => Answer::String("no guard falls asleep".to_string()),
+        }
+    }
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u32 = 4;
+    const INPUT: &'static str = include_str!("input.txt");
+    const ANSWERS: &'static str = include_str!("answers.txt");
+
+    type Input = HashMap<u32, [u32; 60]>;
+    type Part1 = Asleep;
+    type Part2 = Asleep;
+
+    fn parse(input: &str) -> parse::Result<HashMap<u32, [u32; 60]>> {
+        let mut records = parse::lines(input)
+            .at_least_one("at least one record")?
+            .map(Record::parse)
+            .collect::<parse::Result<Vec<_>>>()?;
+        records.sort_by_key(|record| record.time);
+
+        let mut sleepytime = HashMap::new();
+        let mut guard = None;
+        let mut asleep = None;
+        for record in records {
+            match (&record.event, guard, asleep) {
+                (&Event::BeginsShift(id), _, None) => {
+                    sleepytime.entry(id).or_insert_with(|| [0; 60]);
+                    guard = Some(id);
+                }
+                (Event::FallsAsleep, Some(_), None) => asleep = Some(record.minute()),
+                (Event::WakesUp, Some(id), Some(sleeps)) => {
+                    let minutes = sleepytime.get_mut(&id).unwrap();
+                    for minute in minutes.iter_mut().take(record.minute()).skip(sleeps) {
+                        *minute += 1;
+                    }
+                    asleep = None;
+                }
+                (_, None, _) => return Err(record.at.error("a guard beginning a shift")),
+                (_, Some(_), None) => {
+                    return Err(record
+                        .at
+                        .error("a guard beginning a shift or `falls asleep`"))
+                }
+                (_, Some(_), Some(_)) => return Err(record.at.error("`wakes up`")),
+            }
+        }
+        Ok(sleepytime)
+    }
+
+    fn part1(sleepytime: &HashMap<u32, [u32; 60]>) -> Asleep {
+        Asleep(strategy(sleepytime, Part::One).map(|(id, minute)| id * minute))
+    }
+
+    fn part2(sleepytime: &HashMap<u32, [u32; 60]>) -> Asleep {
+        Asleep(strategy(sleepytime, Part::Two).map(|(id, minute)| id * minute))
+    }
+
+    fn explain(sleepytime: &HashMap<u32, [u32; 60]>, part: Part) -> Vec<Fact> {
+        let (id, minute) = match strategy(sleepytime, part) {
+            Some(choice) => choice,
+            None => return Vec::new(),
+        };
+        let minutes = &sleepytime[&id];
+        vec![
+            Fact::new("guard", id),
+            Fact::new("minutes asleep", minutes.iter().sum::<u32>()),
+            Fact::new("minute", minute),
+            Fact::new("times asleep that minute", minutes[minute as usize]),
+        ]
+    }
+}
+
+/// The guard and minute picked by strategy 1 (the guard asleep the most
+/// minutes) or strategy 2 (the guard most often asleep on the same minute),
+/// and the minute that guard is most often asleep, or `None` if no guard
+/// ever falls asleep. Ties go to the lowest ID and the earliest minute.
+fn strategy(sleepytime: &HashMap<u32, [u32; 60]>, part: Part) -> Option<(u32, u32)> {
+    let (&id, minutes) = sleepytime
+        .iter()
+        .filter(|(_, minutes)| minutes.iter().any(|&times| times > 0))
+        .max_by_key(|&(&id, minutes)| {
+            let sleepiness = match part {
+                Part::One => minutes.iter().sum::<u32>(),
+                Part::Two => minutes.iter().copied().max().unwrap_or(0),
+            };
+            (sleepiness, Reverse(id))
+        })?;
+    let minute = (0..60).max_by_key(|&i| (minutes[i as usize], Reverse(i)))?;
+    Some((id, minute))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:25] wakes up
+[1518-11-01 00:30] falls asleep
+[1518-11-01 00:55] wakes up
+[1518-11-01 23:58] Guard #99 begins shift
+[1518-11-02 00:40] falls asleep
+[1518-11-02 00:50] wakes up
+[1518-11-03 00:05] Guard #10 begins shift
+[1518-11-03 00:24] falls asleep
+[1518-11-03 00:29] wakes up
+[1518-11-04 00:02] Guard #99 begins shift
+[1518-11-04 00:36] falls asleep
+[1518-11-04 00:46] wakes up
+[1518-11-05 00:03] Guard #99 begins shift
+[1518-11-05 00:45] falls asleep
+[1518-11-05 00:55] wakes up";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            Day04::part1(&Day04::parse(EXAMPLE).unwrap()),
+            Asleep(Some(240))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            Day04::part2(&Day04::parse(EXAMPLE).unwrap()),
+            Asleep(Some(4455))
+        );
+    }
+
+    #[test]
+    fn explains_the_guard_and_minute() {
+        let sleepytime = Day04::parse(EXAMPLE).unwrap();
+        let explanation: Vec<_> = Day04::explain(&sleepytime, Part::Two)
+            .iter()
+            .map(Fact::to_string)
+            .collect();
+        assert_eq!(
+            explanation,
+            [
+                "guard: 99",
+                "minutes asleep: 30",
+                "minute: 45",
+                "times asleep that minute: 3"
+            ]
+        );
+    }
+
+    #[test]
+    fn ties_and_guards_who_never_sleep() {
+        // #7 and #3 both sleep for 10 minutes, each minute once, and #1 never
+        // sleeps
+        let sleepytime = Day04::parse(
+            "[1518-11-01 00:00] Guard #7 begins shift
+[1518-11-01 00:10] falls asleep
+[1518-11-01 00:15] wakes up
+[1518-11-01 00:20] falls asleep
+[1518-11-01 00:25] wakes up
+[1518-11-02 00:00] Guard #3 begins shift
+[1518-11-02 00:30] falls asleep
+[1518-11-02 00:40] wakes up
+[1518-11-03 00:00] Guard #1 begins shift",
+        )
+        .unwrap();
+        assert_eq!(Day04::part1(&sleepytime), Asleep(Some(3 * 30)));
+        assert_eq!(Day04::part2(&sleepytime), Asleep(Some(3 * 30)));
+        let sleepytime = Day04::parse("[1518-11-01 00:00] Guard #7 begins shift").unwrap();
+        assert_eq!(
+            Answer::from(Day04::part2(&sleepytime)).to_string(),
+            "no guard falls asleep"
+        );
+        assert!(Day04::explain(&sleepytime, Part::One).is_empty());
+    }
+}
@@ -0,0 +1,78 @@
+use crate::{parse, Solution};
+
+pub(crate) mod reference;
+
+/// Reacts `units` in one pass, keeping the units that are left so far on a
+/// stack: each new unit either reacts with the top one or is pushed.
+pub(crate) fn react(units: impl Iterator<Item = char>) -> String {
+    let mut left = String::new();
+    for unit in units {
+        match left.chars().next_back() {
+            Some(last) if last != unit && last.eq_ignore_ascii_case(&unit) => {
+                left.pop();
+            }
+            _ => left.push(unit),
+        }
+    }
+    left
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u32 = 5;
+    const INPUT: &'static str = include_str!("input.txt");
+    const ANSWERS: &'static str = include_str!("answers.txt");
+
+    type Input = String;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> parse::Result<String> {
+        let mut line = parse::lines(input.trim()).expect("a polymer")?;
+        let units = line.cells("a unit letter", |c| {
+            Some(c).filter(char::is_ascii_alphabetic)
+        })?;
+        Ok(units.into_iter().collect())
+    }
+
+    fn part1(polymer: &String) -> usize {
+        react(polymer.chars()).len()
+    }
+
+    fn part2(polymer: &String) -> usize {
+        // removing a unit can't undo any reaction, so start from the reacted
+        // polymer rather than the whole thing
+        let reacted = react(polymer.chars());
+        (b'a'..=b'z')
+            .map(|c| {
+                let units = reacted
+                    .chars()
+                    .filter(|d| c as char != d.to_ascii_lowercase());
+                react(units).len()
+            })
+            .min()
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn react_examples() {
+        for react in &[|s: &str| react(s.chars()), reference::react] {
+            assert_eq!(react("aA"), "");
+            assert_eq!(react("abBA"), "");
+            assert_eq!(react("abAB"), "abAB");
+            assert_eq!(react("aabAAB"), "aabAAB");
+            assert_eq!(react("dabAcCaCBAcCcaDA"), "dabCBAcaDA");
+        }
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day05::part2(&Day05::parse("dabAcCaCBAcCcaDA").unwrap()), 4);
+    }
+}
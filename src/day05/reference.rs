@@ -1,11 +1,10 @@
-use lazy_static::lazy_static;
-use std::mem::swap;
+//! The first, straightforward solution, kept to check the faster one against.
 
-lazy_static! {
-    static ref POLYMER: &'static str = include_str!("input.txt").trim();
-}
+use std::mem::swap;
 
-fn react(s: &str) -> String {
+/// Reacts `s` by removing reacting pairs pass after pass until a pass finds
+/// none.
+pub(crate) fn react(s: &str) -> String {
     let mut curr = s.to_string();
     let mut next = String::with_capacity(curr.len());
     loop {
@@ -37,19 +36,9 @@ fn react(s: &str) -> String {
     }
 }
 
-fn part1() {
-    println!("{}", react(*POLYMER).len());
-}
-
-fn part2() {
-    let result = (b'a'..=b'z')
-        .map(|c| react(&POLYMER.replace(|d: char| c as char == d.to_ascii_lowercase(), "")).len())
+pub(crate) fn part2(polymer: &str) -> usize {
+    (b'a'..=b'z')
+        .map(|c| react(&polymer.replace(|d: char| c as char == d.to_ascii_lowercase(), "")).len())
         .min()
-        .unwrap();
-    println!("{}", result);
-}
-
-fn main() {
-    part1();
-    part2();
+        .unwrap()
 }
@@ -0,0 +1,202 @@
+use crate::{
+    answer::{Answer, Fact},
+    iter::IteratorExt,
+    params::{Param, Params},
+    parse,
+    registry::Part,
+    Solution,
+};
+use itertools::iproduct;
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+};
+
+type Point = (i32, i32);
+
+fn manhattan((x1, y1): Point, (x2, y2): Point) -> i32 {
+    (x1 - x2).abs() + (y1 - y2).abs()
+}
+
+const GRID_SIZE: Param = Param {
+    name: "grid_size",
+    doc: "How far right and down from 0,0 the region to search goes",
+    default: 500,
+    min: 1,
+    max: u64::MAX,
+};
+
+const MAX_DISTANCE: Param = Param {
+    name: "max_distance",
+    doc: "Part 2 counts locations whose total distance is less than this",
+    default: 10000,
+    min: 1,
+    max: u64::MAX,
+};
+
+pub struct Coordinates {
+    points: Vec<Point>,
+    grid_size: i32,
+    max_distance: i32,
+}
+
+/// The largest area that isn't infinite, if any coordinate has one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FiniteArea(pub Option<i32>);
+
+impl From<FiniteArea> for Answer {
+    fn from(area: FiniteArea) -> Self {
+        match area.0 {
+            Some(area) => area.into(),
+            None => Answer::String("no finite area".to_string()),
+        }
+    }
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u32 = 6;
+    const INPUT: &'static str = include_str!("input.txt");
+    const ANSWERS: &'static str = include_str!("answers.txt");
+
+    const PARAMS: &'static [Param] = &[GRID_SIZE, MAX_DISTANCE];
+
+    type Input = Coordinates;
+    type Part1 = FiniteArea;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> parse::Result<Coordinates> {
+        let points = parse::lines(input)
+            .at_least_one("at least one coordinate")?
+            .map(|mut line| {
+                let x = line.number()?;
+                line.literal(",")?;
+                let y = line.number()?;
+                line.end()?;
+                Ok((x, y))
+            })
+            .collect::<parse::Result<_>>()?;
+        Ok(Coordinates {
+            points,
+            grid_size: GRID_SIZE.default as i32,
+            max_distance: MAX_DISTANCE.default as i32,
+        })
+    }
+
+    fn configure(coordinates: &mut Coordinates, params: &Params) {
+        coordinates.grid_size = params.get(&GRID_SIZE) as i32;
+        coordinates.max_distance = params.get(&MAX_DISTANCE) as i32;
+    }
+
+    fn part1(coordinates: &Coordinates) -> FiniteArea {
+        let (largest, _) = largest_finite_area(coordinates);
+        FiniteArea(largest.map(|(_, area)| area))
+    }
+
+    fn part2(coordinates: &Coordinates) -> usize {
+        let size = coordinates.grid_size;
+        iproduct!(0..size, 0..size)
+            .filter(|&a| {
+                let total: i32 = coordinates.points.iter().map(|&b| manhattan(a, b)).sum();
+                total < coordinates.max_distance
+            })
+            .count()
+    }
+
+    fn explain(coordinates: &Coordinates, part: Part) -> Vec<Fact> {
+        match part {
+            Part::One => {
+                let (largest, infinite) = largest_finite_area(coordinates);
+                let mut facts: Vec<_> = largest
+                    .map(|(coordinate, _)| Fact::new("coordinate", coordinate))
+                    .into_iter()
+                    .collect();
+                facts.push(Fact::new("coordinates with infinite areas", infinite));
+                facts
+            }
+            Part::Two => Vec::new(),
+        }
+    }
+}
+
+/// The coordinate with the largest area that isn't infinite and that area,
+/// if there is one, and how many coordinates have infinite areas. Of
+/// coordinates with equally large areas, the one listed first wins.
+fn largest_finite_area(coordinates: &Coordinates) -> (Option<(Point, i32)>, usize) {
+    let size = coordinates.grid_size;
+    let mut hull = HashSet::new();
+    let mut areas = HashMap::new();
+    for (i, j) in iproduct!(0..size, 0..size) {
+        if let Some(closest) = coordinates
+            .points
+            .iter()
+            .single_min_by_key(|&&point| manhattan(point, (i, j)))
+        {
+            if i == 0 || i == size - 1 || j == 0 || j == size - 1 {
+                hull.insert(closest);
+            }
+            *areas.entry(closest).or_insert(0) += 1;
+        }
+    }
+    let largest = coordinates
+        .points
+        .iter()
+        .enumerate()
+        .filter(|(_, point)| !hull.contains(point))
+        .filter_map(|(i, point)| Some((i, *point, *areas.get(point)?)))
+        .max_by_key(|&(i, _, area)| (area, Reverse(i)))
+        .map(|(_, point, area)| (point, area));
+    (largest, hull.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        let coordinates = Day06::parse("1, 1\n1, 6\n8, 3\n3, 4\n5, 5\n8, 9").unwrap();
+        assert_eq!(Day06::part1(&coordinates), FiniteArea(Some(17)));
+        assert_eq!(
+            Day06::explain(&coordinates, Part::One)[0],
+            Fact::new("coordinate", (5, 5))
+        );
+    }
+
+    #[test]
+    fn no_finite_area() {
+        let coordinates = Day06::parse("1, 1").unwrap();
+        assert_eq!(Day06::part1(&coordinates), FiniteArea(None));
+        assert_eq!(
+            Day06::explain(&coordinates, Part::One),
+            [Fact::new("coordinates with infinite areas", 1)]
+        );
+        let mut coordinates = Day06::parse("1, 1\n1, 6\n8, 3\n3, 4\n5, 5\n8, 9").unwrap();
+        Day06::configure(&mut coordinates, &Params::with(&[(GRID_SIZE, 1)]));
+        assert_eq!(
+            Answer::from(Day06::part1(&coordinates)).to_string(),
+            "no finite area"
+        );
+    }
+
+    #[test]
+    fn ties_go_to_the_first_coordinate() {
+        // 3,3 and 7,3 have the same 13 locations closest to them
+        let coordinates = Day06::parse("7, 3\n3, 3\n5, 0\n5, 6\n0, 3\n10, 3").unwrap();
+        assert_eq!(
+            Day06::explain(&coordinates, Part::One)[0],
+            Fact::new("coordinate", (7, 3))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        let mut coordinates = Day06::parse("1, 1\n1, 6\n8, 3\n3, 4\n5, 5\n8, 9").unwrap();
+        Day06::configure(
+            &mut coordinates,
+            &Params::with(&[(MAX_DISTANCE, 32), (GRID_SIZE, 10)]),
+        );
+        assert_eq!(Day06::part2(&coordinates), 16);
+    }
+}
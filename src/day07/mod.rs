@@ -0,0 +1,239 @@
+use crate::{
+    answer::Answer,
+    params::{Param, Params},
+    parse::{self, Line},
+    Solution,
+};
+use itertools::Itertools;
+use std::{collections::HashMap, convert::TryFrom};
+
+pub struct Step {
+    name: char,
+    prereqs: String,
+}
+
+impl Step {
+    fn new(name: char) -> Self {
+        Step {
+            name,
+            prereqs: String::new(),
+        }
+    }
+
+    fn is_ready(&self, started: &str, completed: &str) -> bool {
+        !started.contains(self.name)
+            && self
+                .prereqs
+                .chars()
+                .all(|prereq| completed.contains(prereq))
+    }
+}
+
+fn step_name(line: &mut Line) -> parse::Result<char> {
+    line.skip_spaces();
+    match line.peek() {
+        Some(c) if c.is_ascii_uppercase() => line.next_char("a step letter"),
+        _ => Err(line.error("a step letter")),
+    }
+}
+
+/// Parses a line like `Step C must be finished before step A can begin.` into
+/// the prerequisite and the step that waits for it.
+fn constraint(mut line: Line) -> parse::Result<(char, char)> {
+    line.literal("Step")?;
+    let prereq = step_name(&mut line)?;
+    line.literal("must be finished before step")?;
+    let step = step_name(&mut line)?;
+    line.literal("can begin.")?;
+    line.end()?;
+    Ok((prereq, step))
+}
+
+const WORKERS: Param = Param {
+    name: "workers",
+    doc: "How many workers, counting you, can work on steps at once",
+    default: 5,
+    min: 1,
+    // there are only 26 steps to work on
+    max: 26,
+};
+
+const BASE_TIME: Param = Param {
+    name: "base_time",
+    doc: "How many seconds every step takes on top of its letter's number",
+    default: 60,
+    min: 0,
+    // low enough that 26 steps one after another still fit in a `u32`
+    max: 100_000_000,
+};
+
+pub struct Instructions {
+    steps: Vec<Step>,
+    workers: usize,
+    base_time: u32,
+}
+
+/// What part 1 or 2 finds, unless the steps wait on each other in a cycle
+/// and can never all be done.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Acyclic<T>(pub Option<T>);
+
+impl<T: Into<Answer>> From<Acyclic<T>> for Answer {
+    fn from(answer: Acyclic<T>) -> Self {
+        match answer.0 {
+            Some(answer) => answer.into(),
+            None => Answer::String("the steps wait on each other in a cycle".to_string()),
+        }
+    }
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u32 = 7;
+    const INPUT: &'static str = include_str!("input.txt");
+    const ANSWERS: &'static str = include_str!("answers.txt");
+
+    const PARAMS: &'static [Param] = &[WORKERS, BASE_TIME];
+
+    type Input = Instructions;
+    type Part1 = Acyclic<String>;
+    type Part2 = Acyclic<u32>;
+
+    fn parse(input: &str) -> parse::Result<Instructions> {
+        let mut map = HashMap::new();
+        for line in parse::lines(input) {
+            let (prereq, step) = constraint(line)?;
+            map.entry(step)
+                .or_insert_with(|| Step::new(step))
+                .prereqs
+                .push(prereq);
+            map.entry(prereq).or_insert_with(|| Step::new(prereq));
+        }
+        Ok(Instructions {
+            steps: map
+                .drain()
+                .map(|(_, step)| step)
+                .sorted_by_key(|step| step.name),
+            workers: WORKERS.default as usize,
+            base_time: BASE_TIME.default as u32,
+        })
+    }
+
+    fn configure(instructions: &mut Instructions, params: &Params) {
+        instructions.workers = params.get(&WORKERS) as usize;
+        instructions.base_time =
+            u32::try_from(params.get(&BASE_TIME)).expect("`BASE_TIME.max` fits in a `u32`");
+    }
+
+    fn part1(instructions: &Instructions) -> Acyclic<String> {
+        let steps = &instructions.steps;
+        let mut order = String::new();
+        while let Some(next_step) = steps
+            .iter()
+            .find(|step| step.is_ready(&order, &order))
+            .map(|step| step.name)
+        {
+            order.push(next_step);
+        }
+        Acyclic(Some(order).filter(|order| order.len() == steps.len()))
+    }
+
+    fn part2(instructions: &Instructions) -> Acyclic<u32> {
+        let steps = &instructions.steps;
+        let time_needed = |step: char| u32::from(step as u8 - b'A' + 1) + instructions.base_time;
+        let mut started = String::new();
+        let mut completed = String::new();
+        let mut time = 0;
+        // the step each worker is on and when they'll finish it
+        let mut workers: Vec<Option<(char, u32)>> = vec![None; instructions.workers];
+
+        while completed.len() < steps.len() {
+            for worker in workers.iter_mut().filter(|worker| worker.is_none()) {
+                let next_step = steps
+                    .iter()
+                    .find(|step| step.is_ready(&started, &completed));
+                if let Some(step) = next_step {
+                    started.push(step.name);
+                    *worker = Some((step.name, time + time_needed(step.name)));
+                }
+            }
+            // skip ahead to when the next steps are done
+            time = match workers.iter().filter_map(|&worker| Some(worker?.1)).min() {
+                Some(done) => done,
+                None => return Acyclic(None),
+            };
+            for worker in &mut workers {
+                if let Some((step, done)) = *worker {
+                    if done == time {
+                        completed.push(step);
+                        *worker = None;
+                    }
+                }
+            }
+        }
+        Acyclic(Some(time))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Step C must be finished before step A can begin.
+Step C must be finished before step F can begin.
+Step A must be finished before step B can begin.
+Step A must be finished before step D can begin.
+Step B must be finished before step E can begin.
+Step D must be finished before step E can begin.
+Step F must be finished before step E can begin.";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            Day07::part1(&Day07::parse(EXAMPLE).unwrap()),
+            Acyclic(Some("CABDFE".to_string()))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        let mut instructions = Day07::parse(EXAMPLE).unwrap();
+        Day07::configure(
+            &mut instructions,
+            &Params::with(&[(WORKERS, 2), (BASE_TIME, 0)]),
+        );
+        assert_eq!(Day07::part2(&instructions), Acyclic(Some(15)));
+    }
+
+    #[test]
+    fn cycle() {
+        let instructions = Day07::parse(
+            "Step A must be finished before step B can begin.\n\
+             Step B must be finished before step A can begin.\n\
+             Step C must be finished before step A can begin.",
+        )
+        .unwrap();
+        assert_eq!(Day07::part1(&instructions), Acyclic(None));
+        assert_eq!(Day07::part2(&instructions), Acyclic(None));
+        assert_eq!(
+            Answer::from(Day07::part2(&instructions)).to_string(),
+            "the steps wait on each other in a cycle"
+        );
+    }
+
+    #[test]
+    fn longest_base_time() {
+        let mut instructions = Day07::parse(EXAMPLE).unwrap();
+        Day07::configure(
+            &mut instructions,
+            &Params::with(&[(WORKERS, 26), (BASE_TIME, u64::MAX)]),
+        );
+        // C, then A, then D, then E, with F and B alongside
+        assert_eq!(
+            Day07::part2(&instructions),
+            Acyclic(Some(400_000_000 + 3 + 1 + 4 + 5))
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use crate::{
+    parse::{self, Line},
+    Solution,
+};
+
+type Datum = u8;
+
+pub struct Node {
+    children: Vec<Node>,
+    metadata: Vec<Datum>,
+}
+
+impl Node {
+    fn build(line: &mut Line) -> parse::Result<Self> {
+        let num_children: Datum = line.number()?;
+        let num_metadata: Datum = line.number()?;
+        Ok(Node {
+            children: (0..num_children)
+                .map(|_| Node::build(line))
+                .collect::<parse::Result<_>>()?,
+            metadata: (0..num_metadata)
+                .map(|_| line.number())
+                .collect::<parse::Result<_>>()?,
+        })
+    }
+
+    fn sum_metadata(&self) -> u32 {
+        let mut sum = self.metadata.iter().map(|&x| u32::from(x)).sum::<u32>();
+        for child in &self.children {
+            sum += child.sum_metadata();
+        }
+        sum
+    }
+
+    fn value(&self) -> u32 {
+        if self.children.is_empty() {
+            self.metadata.iter().map(|&x| u32::from(x)).sum::<u32>()
+        } else {
+            self.metadata
+                .iter()
+                .filter(|&&i| i > 0 && i as usize <= self.children.len())
+                .fold(0, |acc, &i| acc + self.children[i as usize - 1].value())
+        }
+    }
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u32 = 8;
+    const INPUT: &'static str = include_str!("input.txt");
+    const ANSWERS: &'static str = include_str!("answers.txt");
+
+    type Input = Node;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> parse::Result<Node> {
+        let mut line = parse::lines(input).expect("the tree")?;
+        let tree = Node::build(&mut line)?;
+        line.end()?;
+        Ok(tree)
+    }
+
+    fn part1(tree: &Node) -> u32 {
+        tree.sum_metadata()
+    }
+
+    fn part2(tree: &Node) -> u32 {
+        tree.value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day08::part1(&Day08::parse(EXAMPLE).unwrap()), 138);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day08::part2(&Day08::parse(EXAMPLE).unwrap()), 66);
+    }
+}
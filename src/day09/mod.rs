@@ -0,0 +1,225 @@
+use crate::{answer::Answer, parse, registry::Part, Resume, Solution};
+use intrusive_collections::{intrusive_adapter, LinkedList, LinkedListLink};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+struct Marble {
+    value: usize,
+    link: LinkedListLink,
+}
+
+impl Marble {
+    fn new(value: usize) -> Box<Self> {
+        Box::new(Marble {
+            value,
+            link: LinkedListLink::new(),
+        })
+    }
+}
+
+intrusive_adapter!(MarbleAdapter = Box<Marble>: Marble { link: LinkedListLink });
+
+pub struct Game {
+    num_players: usize,
+    num_marbles: usize,
+}
+
+impl Game {
+    fn new(num_players: usize, num_marbles: usize) -> Self {
+        Game {
+            num_players,
+            num_marbles,
+        }
+    }
+
+    fn high_score(&self) -> usize {
+        let mut circle = Circle::new(self.num_players);
+        while circle.play(self) {}
+        circle.high_score()
+    }
+}
+
+/// A game partway through: the marbles placed so far, clockwise from the
+/// one after the current marble, which is last.
+pub struct Circle {
+    marbles: LinkedList<MarbleAdapter>,
+    scores: Vec<usize>,
+    next_marble: usize,
+}
+
+impl Circle {
+    fn new(num_players: usize) -> Self {
+        let mut marbles = LinkedList::new(MarbleAdapter::new());
+        marbles.push_back(Marble::new(0));
+        Circle {
+            marbles,
+            scores: vec![0; num_players],
+            next_marble: 1,
+        }
+    }
+
+    /// Moves the current marble `n` marbles clockwise.
+    fn clockwise(&mut self, n: usize) {
+        for _ in 0..n {
+            let marble = self.marbles.pop_front().unwrap();
+            self.marbles.push_back(marble);
+        }
+    }
+
+    fn counter_clockwise(&mut self, n: usize) {
+        for _ in 0..n {
+            let marble = self.marbles.pop_back().unwrap();
+            self.marbles.push_front(marble);
+        }
+    }
+
+    /// Places the next marble, or returns `false` if they've all been
+    /// placed.
+    fn play(&mut self, game: &Game) -> bool {
+        let marble = self.next_marble;
+        if marble > game.num_marbles {
+            return false;
+        }
+        if marble.is_multiple_of(23) {
+            self.counter_clockwise(7);
+            let removed = self.marbles.pop_back().unwrap();
+            let player = (marble - 1) % self.scores.len();
+            self.scores[player] += marble + removed.value;
+            self.clockwise(1);
+        } else {
+            self.clockwise(1);
+            self.marbles.push_back(Marble::new(marble));
+        }
+        self.next_marble += 1;
+        true
+    }
+
+    fn high_score(&self) -> usize {
+        self.scores.iter().copied().max().unwrap_or(0)
+    }
+}
+
+/// How a [`Circle`] is saved, since its marbles are linked together.
+#[derive(Serialize, Deserialize)]
+struct SavedCircle {
+    marbles: Vec<usize>,
+    scores: Vec<usize>,
+    next_marble: usize,
+}
+
+impl Serialize for Circle {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SavedCircle {
+            marbles: self.marbles.iter().map(|marble| marble.value).collect(),
+            scores: self.scores.clone(),
+            next_marble: self.next_marble,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Circle {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let saved = SavedCircle::deserialize(deserializer)?;
+        if saved.marbles.is_empty() || saved.scores.is_empty() {
+            return Err(de::Error::custom("a game needs marbles and players"));
+        }
+        let mut marbles = LinkedList::new(MarbleAdapter::new());
+        for value in saved.marbles {
+            marbles.push_back(Marble::new(value));
+        }
+        Ok(Circle {
+            marbles,
+            scores: saved.scores,
+            next_marble: saved.next_marble,
+        })
+    }
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u32 = 9;
+    const INPUT: &'static str = include_str!("input.txt");
+    const ANSWERS: &'static str = include_str!("answers.txt");
+
+    type Input = Game;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> parse::Result<Game> {
+        let mut line = parse::lines(input).expect("the game")?;
+        line.skip_spaces();
+        let at = line;
+        let num_players = line.number()?;
+        if num_players == 0 {
+            return Err(at.error("at least one player"));
+        }
+        line.literal("players; last marble is worth")?;
+        let num_marbles = line.number()?;
+        line.literal("points")?;
+        line.end()?;
+        Ok(Game::new(num_players, num_marbles))
+    }
+
+    fn part1(game: &Game) -> usize {
+        game.high_score()
+    }
+
+    fn part2(game: &Game) -> usize {
+        Game::new(game.num_players, game.num_marbles * 100).high_score()
+    }
+}
+
+impl Resume for Day09 {
+    type State = Circle;
+    type Context = Game;
+
+    fn start(game: &Game, part: Part) -> Option<(Circle, Game)> {
+        let num_marbles = match part {
+            Part::One => game.num_marbles,
+            Part::Two => game.num_marbles * 100,
+        };
+        let game = Game::new(game.num_players, num_marbles);
+        Some((Circle::new(game.num_players), game))
+    }
+
+    fn advance(circle: &mut Circle, game: &Game) -> bool {
+        circle.play(game)
+    }
+
+    fn finish(circle: &Circle, _: &Game) -> Answer {
+        circle.high_score().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_examples() {
+        for &(players, marbles, score) in &[
+            (9, 25, 32),
+            (10, 1618, 8317),
+            (13, 7999, 146_373),
+            (17, 1104, 2764),
+            (21, 6111, 54718),
+            (30, 5807, 37305),
+        ] {
+            let input = format!(
+                "{} players; last marble is worth {} points",
+                players, marbles
+            );
+            assert_eq!(Day09::part1(&Day09::parse(&input).unwrap()), score);
+        }
+    }
+
+    #[test]
+    fn no_players() {
+        let error = Day09::parse("0 players; last marble is worth 25 points")
+            .err()
+            .unwrap();
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(error.expected, "at least one player");
+    }
+}
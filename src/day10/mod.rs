@@ -0,0 +1,311 @@
+use crate::{
+    answer::Answer,
+    grid::Grid,
+    params::{Param, Params},
+    parse::{self, Line},
+    visualize::{Frame, Rgb, Style},
+    Render, Solution,
+};
+use itertools::Itertools;
+use std::{collections::HashSet, iter, time::Duration};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Star {
+    pos: (i32, i32),
+    vel: (i32, i32),
+}
+
+impl Star {
+    /// Parses a line like `position=< 9,  1> velocity=< 0,  2>`.
+    fn parse(mut line: Line) -> parse::Result<Self> {
+        line.literal("position=<")?;
+        let x = line.number()?;
+        line.literal(",")?;
+        let y = line.number()?;
+        line.literal(">")?;
+        line.literal("velocity=<")?;
+        let dx = line.number()?;
+        line.literal(",")?;
+        let dy = line.number()?;
+        line.literal(">")?;
+        line.end()?;
+        Ok(Star {
+            pos: (x, y),
+            vel: (dx, dy),
+        })
+    }
+
+    fn take_steps(&mut self, steps: i32) {
+        self.pos = (
+            self.pos.0 + self.vel.0 * steps,
+            self.pos.1 + self.vel.1 * steps,
+        );
+    }
+}
+
+struct Rect {
+    top: i32,
+    left: i32,
+    bottom: i32,
+    right: i32,
+}
+
+impl Rect {
+    fn area(&self) -> i64 {
+        i64::from(self.right - self.left) * i64::from(self.bottom - self.top)
+    }
+
+    fn cells(&self) -> i64 {
+        i64::from(self.right - self.left + 1) * i64::from(self.bottom - self.top + 1)
+    }
+}
+
+fn get_bbox(stars: &[Star]) -> Rect {
+    let (left, right) = stars
+        .iter()
+        .map(|s| s.pos.0)
+        .minmax()
+        .into_option()
+        .unwrap();
+    let (top, bottom) = stars
+        .iter()
+        .map(|s| s.pos.1)
+        .minmax()
+        .into_option()
+        .unwrap();
+    Rect {
+        top,
+        left,
+        bottom,
+        right,
+    }
+}
+
+fn render(stars: &[Star], bbox: &Rect) -> Grid<&'static str> {
+    let positions: HashSet<_> = stars.iter().map(|s| s.pos).collect();
+    let (width, height) = (bbox.right - bbox.left + 1, bbox.bottom - bbox.top + 1);
+    Grid::from_fn(width as usize, height as usize, |(x, y)| {
+        let pos = (bbox.left + x as i32, bbox.top + y as i32);
+        if positions.contains(&pos) {
+            "██"
+        } else {
+            "  "
+        }
+    })
+}
+
+/// The stars in `bbox`, with as much sky again around it.
+fn frame(stars: &[Star], bbox: &Rect) -> Frame {
+    let (width, height) = (bbox.right - bbox.left + 1, bbox.bottom - bbox.top + 1);
+    let (left, top) = (bbox.left - width / 2, bbox.top - height / 2);
+    let mut frame = Grid::new(width as usize * 2, height as usize * 2, 0);
+    for star in stars {
+        let (x, y) = (star.pos.0 - left, star.pos.1 - top);
+        if x >= 0 && y >= 0 {
+            if let Some(cell) = frame.get_mut((x as usize, y as usize)) {
+                *cell = 1;
+            }
+        }
+    }
+    frame
+}
+
+const HORIZON: Param = Param {
+    name: "horizon",
+    doc: "How many seconds to watch the stars for the message",
+    default: 20000,
+    min: 1,
+    max: u64::MAX,
+};
+
+/// The most cells the stars can spread over and still spell a message.
+const MAX_CELLS: i64 = 1 << 20;
+
+pub struct Sky {
+    stars: Vec<Star>,
+    horizon: usize,
+}
+
+/// The second within the horizon when the stars are closest together, which
+/// is when they spell the message, with where they are then and their
+/// bounding box. `None` if they're still closing in at the horizon, or never
+/// get close enough to spell anything.
+fn find_message(sky: &Sky) -> Option<(usize, Vec<Star>, Rect)> {
+    let mut stars = sky.stars.clone();
+    let mut bboxes = Vec::with_capacity(sky.horizon + 1);
+    bboxes.push(get_bbox(&stars));
+    for _ in 0..sky.horizon {
+        for star in &mut stars {
+            star.take_steps(1);
+        }
+        bboxes.push(get_bbox(&stars));
+    }
+    let (step, _) = bboxes
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, bbox)| bbox.area())
+        .unwrap();
+    let bbox = bboxes.swap_remove(step);
+    if step == sky.horizon || bbox.cells() > MAX_CELLS {
+        return None;
+    }
+
+    for star in &mut stars {
+        star.take_steps(step as i32 - sky.horizon as i32);
+    }
+    Some((step, stars, bbox))
+}
+
+/// An answer that's only there if the stars spell a message within the
+/// horizon.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sighting<T>(pub Option<T>);
+
+impl<T: Into<Answer>> From<Sighting<T>> for Answer {
+    fn from(sighting: Sighting<T>) -> Self {
+        match sighting.0 {
+            Some(answer) => answer.into(),
+            None => Answer::String("no message within the horizon".to_string()),
+        }
+    }
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+    const INPUT: &'static str = include_str!("input.txt");
+    const ANSWERS: &'static str = include_str!("answers.txt");
+
+    const PARAMS: &'static [Param] = &[HORIZON];
+
+    type Input = Sky;
+    type Part1 = Sighting<Grid<&'static str>>;
+    type Part2 = Sighting<usize>;
+
+    fn parse(input: &str) -> parse::Result<Sky> {
+        Ok(Sky {
+            stars: parse::lines(input)
+                .at_least_one("at least one star")?
+                .map(Star::parse)
+                .collect::<parse::Result<_>>()?,
+            horizon: HORIZON.default as usize,
+        })
+    }
+
+    fn configure(sky: &mut Sky, params: &Params) {
+        sky.horizon = params.get(&HORIZON) as usize;
+    }
+
+    fn part1(sky: &Sky) -> Sighting<Grid<&'static str>> {
+        Sighting(find_message(sky).map(|(_, stars, bbox)| render(&stars, &bbox)))
+    }
+
+    fn part2(sky: &Sky) -> Sighting<usize> {
+        Sighting(find_message(sky).map(|(step, _, _)| step))
+    }
+}
+
+impl Render for Day10 {
+    const STYLE: Style = Style {
+        palette: &[Rgb(0, 0, 0x20), Rgb(0xff, 0xf0, 0xa0)],
+        delay: Duration::from_millis(300),
+    };
+
+    /// The last few steps before the message appears, then the message for
+    /// a while. Nothing if there's no message.
+    fn frames(sky: &Sky) -> Box<dyn Iterator<Item = Frame> + '_> {
+        let (step, stars, bbox) = match find_message(sky) {
+            Some(message) => message,
+            None => return Box::new(iter::empty()),
+        };
+        let before = step.min(10) as i32;
+        Box::new((-before..10).map(move |offset| {
+            let mut stars = stars.clone();
+            for star in &mut stars {
+                star.take_steps(offset.min(0));
+            }
+            frame(&stars, &bbox)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+position=< 9,  1> velocity=< 0,  2>
+position=< 7,  0> velocity=<-1,  0>
+position=< 3, -2> velocity=<-1,  1>
+position=< 6, 10> velocity=<-2, -1>
+position=< 2, -4> velocity=< 2,  2>
+position=<-6, 10> velocity=< 2, -2>
+position=< 1,  8> velocity=< 1, -1>
+position=< 1,  7> velocity=< 1,  0>
+position=<-3, 11> velocity=< 1, -2>
+position=< 7,  6> velocity=<-1, -1>
+position=<-2,  3> velocity=< 1,  0>
+position=<-4,  3> velocity=< 2,  0>
+position=<10, -3> velocity=<-1,  1>
+position=< 5, 11> velocity=< 1, -2>
+position=< 4,  7> velocity=< 0, -1>
+position=< 8, -2> velocity=< 0,  1>
+position=<15,  0> velocity=<-2,  0>
+position=< 1,  6> velocity=< 1,  0>
+position=< 8,  9> velocity=< 0, -1>
+position=< 3,  3> velocity=<-1,  1>
+position=< 0,  5> velocity=< 0, -1>
+position=<-2,  2> velocity=< 2,  0>
+position=< 5, -2> velocity=< 1,  2>
+position=< 1,  4> velocity=< 2,  1>
+position=<-2,  7> velocity=< 2, -2>
+position=< 3,  6> velocity=<-1, -1>
+position=< 5,  0> velocity=< 1,  0>
+position=<-6,  0> velocity=< 2,  0>
+position=< 5,  9> velocity=< 1, -2>
+position=<14,  7> velocity=<-2,  0>
+position=<-3,  6> velocity=< 2, -1>";
+
+    #[test]
+    fn part1_example() {
+        let message = Day10::part1(&Day10::parse(EXAMPLE).unwrap()).0.unwrap();
+        let message = message.to_string();
+        let message: Vec<_> = message.lines().map(str::trim_end).collect();
+        assert_eq!(
+            message,
+            [
+                "██      ██    ██████",
+                "██      ██      ██",
+                "██      ██      ██",
+                "██████████      ██",
+                "██      ██      ██",
+                "██      ██      ██",
+                "██      ██      ██",
+                "██      ██    ██████",
+            ]
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            Day10::part2(&Day10::parse(EXAMPLE).unwrap()),
+            Sighting(Some(3))
+        );
+    }
+
+    #[test]
+    fn horizon_before_message() {
+        let mut sky = Day10::parse(EXAMPLE).unwrap();
+        sky.horizon = 3;
+        assert_eq!(Day10::part2(&sky), Sighting(None));
+        assert_eq!(
+            Answer::from(Day10::part1(&sky)).to_string(),
+            "no message within the horizon"
+        );
+        assert_eq!(Day10::frames(&sky).count(), 0);
+        sky.horizon = 4;
+        assert_eq!(Day10::part2(&sky), Sighting(Some(3)));
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{parse, Solution};
+use itertools::iproduct;
+use std::cmp::max;
+
+pub(crate) mod reference;
+
+fn get_power(serial_number: i32, (x, y): (i32, i32)) -> i32 {
+    let rack_id = x + 10;
+    (rack_id * y + serial_number) * rack_id / 100 % 10 - 5
+}
+
+/// The total power of every rectangle from the top left corner of the grid,
+/// so that any square's power takes four lookups.
+struct SummedArea(Vec<i32>);
+
+impl SummedArea {
+    fn new(serial_number: i32) -> Self {
+        let mut sums = vec![0; 301 * 301];
+        for (y, x) in iproduct!(1..301, 1..301) {
+            sums[y * 301 + x] = get_power(serial_number, (x as i32, y as i32))
+                + sums[(y - 1) * 301 + x]
+                + sums[y * 301 + x - 1]
+                - sums[(y - 1) * 301 + x - 1];
+        }
+        SummedArea(sums)
+    }
+
+    /// The power of the square whose top left cell is `(x, y)`.
+    fn square(&self, x: usize, y: usize, size: usize) -> i32 {
+        let sum = |x: usize, y: usize| self.0[y * 301 + x];
+        let (x0, y0, x1, y1) = (x - 1, y - 1, x + size - 1, y + size - 1);
+        sum(x1, y1) - sum(x0, y1) - sum(x1, y0) + sum(x0, y0)
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+    const INPUT: &'static str = include_str!("input.txt");
+    const ANSWERS: &'static str = include_str!("answers.txt");
+
+    type Input = i32;
+    type Part1 = (i32, i32);
+    type Part2 = (i32, i32, i32);
+
+    fn parse(input: &str) -> parse::Result<i32> {
+        let mut line = parse::lines(input).expect("a serial number")?;
+        let serial_number = line.number()?;
+        line.end()?;
+        Ok(serial_number)
+    }
+
+    fn part1(&serial_number: &i32) -> (i32, i32) {
+        let (x, y) = iproduct!(1..299, 1..299)
+            .max_by_key(|&(x, y)| {
+                iproduct!(x..x + 3, y..y + 3)
+                    .map(|pos| get_power(serial_number, pos))
+                    .sum::<i32>()
+            })
+            .unwrap();
+        (x, y)
+    }
+
+    fn part2(&serial_number: &i32) -> (i32, i32, i32) {
+        let sums = SummedArea::new(serial_number);
+        // the same order as the reference, so that ties go the same way
+        let (x, y, size) = iproduct!(1..301, 1..301)
+            .flat_map(|(x, y)| (1..(302 - max(x, y))).map(move |size| (x, y, size)))
+            .max_by_key(|&(x, y, size)| sums.square(x, y, size))
+            .unwrap();
+        (x as i32, y as i32, size as i32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn power_examples() {
+        assert_eq!(get_power(8, (3, 5)), 4);
+        assert_eq!(get_power(57, (122, 79)), -5);
+        assert_eq!(get_power(39, (217, 196)), 0);
+        assert_eq!(get_power(71, (101, 153)), 4);
+    }
+
+    #[test]
+    fn part1_examples() {
+        assert_eq!(Day11::part1(&18), (33, 45));
+        assert_eq!(Day11::part1(&42), (21, 61));
+    }
+
+    #[test]
+    fn part2_examples() {
+        assert_eq!(Day11::part2(&18), (90, 269, 16));
+        assert_eq!(Day11::part2(&42), (232, 251, 12));
+    }
+}
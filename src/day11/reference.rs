@@ -0,0 +1,25 @@
+//! The first, straightforward solution, kept to check the faster one against.
+
+use super::get_power;
+use itertools::iproduct;
+use std::cmp::max;
+
+/// Grows each square from its top left corner one row and column at a time,
+/// adding up the new cells.
+pub(crate) fn part2(serial_number: i32) -> (i32, i32, i32) {
+    let (x, y, size, _) = iproduct!(1..301, 1..301)
+        .flat_map(|(x, y)| {
+            (1..(302 - max(x, y))).scan(0, move |power, size| {
+                *power += (x..x + size)
+                    .map(|x| get_power(serial_number, (x, y + size - 1)))
+                    .sum::<i32>();
+                *power += (y..y + size - 1)
+                    .map(|y| get_power(serial_number, (x + size - 1, y)))
+                    .sum::<i32>();
+                Some((x, y, size, *power))
+            })
+        })
+        .max_by_key(|&(_, _, _, power)| power)
+        .unwrap();
+    (x, y, size)
+}
@@ -0,0 +1,206 @@
+use crate::{
+    cycle,
+    params::{Param, Params},
+    parse::{self, Line},
+    Solution,
+};
+
+pub(crate) mod reference;
+
+/// A row of pots from the first with a plant to the last.
+#[derive(Debug, Clone)]
+struct State {
+    first: i64,
+    pots: Vec<bool>,
+}
+
+impl State {
+    fn new(first: i64, mut pots: Vec<bool>) -> Self {
+        let empty = pots.iter().take_while(|&&has_plant| !has_plant).count();
+        pots.drain(..empty);
+        while pots.last() == Some(&false) {
+            pots.pop();
+        }
+        State {
+            first: first + empty as i64,
+            pots,
+        }
+    }
+
+    fn positions(&self) -> impl Iterator<Item = i64> + '_ {
+        (self.first..)
+            .zip(&self.pots)
+            .filter_map(|(pos, &has_plant)| if has_plant { Some(pos) } else { None })
+    }
+
+    /// Slides a window of five pots along the row, from two before the first
+    /// plant to two after the last.
+    fn next_state(&self, grows: &[bool; 32]) -> State {
+        let mut pots = Vec::with_capacity(self.pots.len() + 4);
+        let mut window = 0;
+        for i in 0..self.pots.len() + 4 {
+            let has_plant = self.pots.get(i).copied().unwrap_or(false);
+            window = (window << 1 | has_plant as usize) & 31;
+            pots.push(grows[window]);
+        }
+        State::new(self.first - 2, pots)
+    }
+
+    fn sum(&self) -> i64 {
+        self.positions().sum()
+    }
+}
+
+struct Rule {
+    pattern: [bool; 5],
+    result: bool,
+}
+
+fn pot(line: &mut Line) -> parse::Result<bool> {
+    let has_plant = match line.peek() {
+        Some('#') => true,
+        Some('.') => false,
+        _ => return Err(line.error("`#` or `.`")),
+    };
+    line.next_char("`#` or `.`")?;
+    Ok(has_plant)
+}
+
+impl Rule {
+    /// Parses a line like `..#.. => #`.
+    fn parse(mut line: Line) -> parse::Result<Self> {
+        let mut pattern = [false; 5];
+        for has_plant in &mut pattern {
+            *has_plant = pot(&mut line)?;
+        }
+        line.literal("=>")?;
+        line.skip_spaces();
+        let result = pot(&mut line)?;
+        line.end()?;
+        Ok(Rule { pattern, result })
+    }
+}
+
+pub struct Pots {
+    initial_state: State,
+    rules: Vec<Rule>,
+    /// Whether a pot grows a plant, indexed by the pots around it read as
+    /// binary, leftmost first.
+    grows: [bool; 32],
+    generations: usize,
+    long_generations: usize,
+}
+
+const GENERATIONS: Param = Param {
+    name: "generations",
+    doc: "How many generations part 1 grows",
+    default: 20,
+    min: 0,
+    max: u64::MAX,
+};
+
+const LONG_GENERATIONS: Param = Param {
+    name: "long_generations",
+    doc: "How many generations part 2 grows",
+    default: 50_000_000_000,
+    min: 0,
+    max: u64::MAX,
+};
+
+/// The sum of the positions with plants after `generations`.
+pub(crate) fn sum_after(pots: &Pots, generations: usize) -> i64 {
+    // thanks to jewel for this idea
+    let state = cycle::fast_forward_drifting(
+        pots.initial_state.clone(),
+        generations,
+        |state| *state = state.next_state(&pots.grows),
+        |state| (state.pots.clone(), state.first),
+        |state, by| state.first += by,
+    );
+    state.sum()
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u32 = 12;
+    const INPUT: &'static str = include_str!("input.txt");
+    const ANSWERS: &'static str = include_str!("answers.txt");
+
+    const PARAMS: &'static [Param] = &[GENERATIONS, LONG_GENERATIONS];
+
+    type Input = Pots;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> parse::Result<Pots> {
+        let mut lines = parse::lines(input);
+        let mut line = lines.expect("the initial state")?;
+        line.literal("initial state:")?;
+        line.skip_spaces();
+        let pots = line.cells("`#` or `.`", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })?;
+        let initial_state = State::new(0, pots);
+        lines.expect("a blank line")?.end()?;
+        let rules: Vec<Rule> = lines.map(Rule::parse).collect::<parse::Result<_>>()?;
+        let mut grows = [false; 32];
+        // the first rule for a pattern is the one that counts
+        for rule in rules.iter().rev() {
+            let pattern = rule.pattern.iter().fold(0, |acc, &p| acc << 1 | p as usize);
+            grows[pattern] = rule.result;
+        }
+        Ok(Pots {
+            initial_state,
+            rules,
+            grows,
+            generations: GENERATIONS.default as usize,
+            long_generations: LONG_GENERATIONS.default as usize,
+        })
+    }
+
+    fn configure(pots: &mut Pots, params: &Params) {
+        pots.generations = params.get(&GENERATIONS) as usize;
+        pots.long_generations = params.get(&LONG_GENERATIONS) as usize;
+    }
+
+    fn part1(pots: &Pots) -> i64 {
+        sum_after(pots, pots.generations)
+    }
+
+    fn part2(pots: &Pots) -> i64 {
+        sum_after(pots, pots.long_generations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+initial state: #..#.#..##......###...###
+
+...## => #
+..#.. => #
+.#... => #
+.#.#. => #
+.#.## => #
+.##.. => #
+.#### => #
+#.#.# => #
+#.### => #
+##.#. => #
+##.## => #
+###.. => #
+###.# => #
+####. => #";
+
+    #[test]
+    fn part1_example() {
+        let pots = Day12::parse(EXAMPLE).unwrap();
+        assert_eq!(Day12::part1(&pots), 325);
+        assert_eq!(reference::sum_after(&pots, 20), 325);
+    }
+}
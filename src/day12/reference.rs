@@ -0,0 +1,80 @@
+//! The first, straightforward solution, kept to check the faster one against.
+
+use super::{Pots, Rule};
+use crate::cycle;
+use std::collections::BTreeSet;
+
+/// The positions of the pots with plants.
+#[derive(Debug, Clone)]
+struct State(BTreeSet<i64>);
+
+impl State {
+    fn has_plant(&self, pos: i64) -> bool {
+        self.0.contains(&pos)
+    }
+
+    fn min(&self) -> i64 {
+        *self.0.iter().next().unwrap()
+    }
+
+    fn max(&self) -> i64 {
+        *self.0.iter().next_back().unwrap()
+    }
+
+    fn matches(&self, pos: i64, rule: &Rule) -> bool {
+        rule.pattern
+            .iter()
+            .zip(pos - 2..)
+            .all(|(&pat, pos)| self.has_plant(pos) == pat)
+    }
+
+    fn next_state(&mut self, rules: &[Rule]) {
+        let mut add = Vec::new();
+        let mut remove = Vec::new();
+        for pos in self.min() - 2..self.max() + 3 {
+            // pots that no rule matches end up empty
+            let grows = rules
+                .iter()
+                .find(|rule| self.matches(pos, rule))
+                .is_some_and(|rule| rule.result);
+            if grows {
+                add.push(pos);
+            } else {
+                remove.push(pos);
+            }
+        }
+        for pos in add {
+            self.0.insert(pos);
+        }
+        for pos in remove {
+            self.0.remove(&pos);
+        }
+    }
+
+    fn sum(&self) -> i64 {
+        self.0.iter().sum()
+    }
+
+    fn signature(&self) -> String {
+        (self.min()..=self.max())
+            .map(|i| if self.has_plant(i) { '#' } else { '.' })
+            .collect()
+    }
+
+    fn shift(&self, amount: i64) -> State {
+        State(self.0.iter().map(|i| i + amount).collect())
+    }
+}
+
+/// The sum of the positions with plants after `generations`.
+pub(crate) fn sum_after(pots: &Pots, generations: usize) -> i64 {
+    let initial_state = State(pots.initial_state.positions().collect());
+    let state = cycle::fast_forward_drifting(
+        initial_state,
+        generations,
+        |state| state.next_state(&pots.rules),
+        |state| (state.signature(), state.min()),
+        |state, by| *state = state.shift(by),
+    );
+    state.sum()
+}
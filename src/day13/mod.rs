@@ -0,0 +1,522 @@
+use crate::{
+    answer::Answer,
+    grid::{reading_order, Grid, Pos},
+    observe::Observer,
+    parse,
+    visualize::{self, Frame, Rgb, Simulation, Style},
+    Render, Simulate, Solution,
+};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy)]
+enum TrackType {
+    Empty,
+    Vertical,     // |
+    Horizontal,   // -
+    Diagonal,     // \
+    Antidiagonal, // /
+    Intersection, // +
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    fn turn_left(self) -> Self {
+        use self::Direction::*;
+        match self {
+            Up => Left,
+            Down => Right,
+            Left => Down,
+            Right => Up,
+        }
+    }
+
+    fn turn_right(self) -> Self {
+        use self::Direction::*;
+        match self {
+            Up => Right,
+            Down => Left,
+            Left => Up,
+            Right => Down,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Cart {
+    pos: Pos,
+    dir: Direction,
+    intersections: usize,
+}
+
+impl Cart {
+    fn new(x: usize, y: usize, dir: Direction) -> Self {
+        Cart {
+            pos: (x, y),
+            dir,
+            intersections: 0,
+        }
+    }
+
+    /// Where the cart moves to, or `None` if that's off the track.
+    fn next_pos(&self, track: &Grid<TrackType>) -> Option<Pos> {
+        let (x, y) = self.pos;
+        let pos = match self.dir {
+            Direction::Up => (x, y.checked_sub(1)?),
+            Direction::Down => (x, y + 1),
+            Direction::Left => (x.checked_sub(1)?, y),
+            Direction::Right => (x + 1, y),
+        };
+        match track.get(pos)? {
+            TrackType::Empty => None,
+            _ => Some(pos),
+        }
+    }
+
+    fn next_dir(&self, track_type: TrackType) -> Direction {
+        use self::{Direction::*, TrackType::*};
+        match (self.dir, track_type) {
+            (dir, Vertical) => dir,
+            (dir, Horizontal) => dir,
+            (Up, Diagonal) => Left,
+            (Down, Diagonal) => Right,
+            (Left, Diagonal) => Up,
+            (Right, Diagonal) => Down,
+            (Up, Antidiagonal) => Right,
+            (Down, Antidiagonal) => Left,
+            (Left, Antidiagonal) => Down,
+            (Right, Antidiagonal) => Up,
+            (dir, Intersection) => match self.intersections % 3 {
+                0 => dir.turn_left(),
+                1 => dir,
+                2 => dir.turn_right(),
+                _ => unreachable!("problem"),
+            },
+            (_, Empty) => unreachable!("`next_pos` keeps carts on the track"),
+        }
+    }
+}
+
+/// Something that happened to a cart during a tick.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    Moved {
+        from: Pos,
+        to: Pos,
+    },
+    /// A cart turned at a curve or an intersection, having moved onto it.
+    Turned {
+        at: Pos,
+        from: Direction,
+        to: Direction,
+    },
+    /// A cart ran into another at `at`, and both were removed.
+    Collided {
+        at: Pos,
+    },
+    /// The cart at `at` was about to run off the track, which stops
+    /// everything.
+    Derailed {
+        at: Pos,
+    },
+}
+
+/// Where part 1's first crash or part 2's last cart is, or why there isn't
+/// one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    At(Pos),
+    /// There are too few carts to crash.
+    NoCrash,
+    /// Every cart crashed.
+    NoCartLeft,
+    /// The cart here was about to run off the track.
+    Derailed(Pos),
+}
+
+impl From<Outcome> for Answer {
+    fn from(outcome: Outcome) -> Self {
+        match outcome {
+            Outcome::At(pos) => pos.into(),
+            Outcome::NoCrash => Answer::String("no crash".to_string()),
+            Outcome::NoCartLeft => Answer::String("no cart left".to_string()),
+            Outcome::Derailed((x, y)) => {
+                Answer::String(format!("a cart left the track at {},{}", x, y))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct State {
+    carts: Vec<Cart>,
+    track: Grid<TrackType>,
+}
+
+impl State {
+    fn parse(input: &str) -> parse::Result<Self> {
+        // trailing spaces are often trimmed from the right of the map
+        let empty = (TrackType::Empty, None);
+        let map = Grid::parse_ragged(input, "track, a cart or a space", empty, |c| {
+            use self::{Direction::*, TrackType::*};
+            Some(match c {
+                ' ' => (Empty, None),
+                '|' => (Vertical, None),
+                '-' => (Horizontal, None),
+                '\\' => (Diagonal, None),
+                '/' => (Antidiagonal, None),
+                '+' => (Intersection, None),
+                // look at these cute friends!!!!!!
+                '^' => (Vertical, Some(Up)),
+                'v' => (Vertical, Some(Down)),
+                '<' => (Horizontal, Some(Left)),
+                '>' => (Horizontal, Some(Right)),
+                _ => return None,
+            })
+        })?;
+        // reading order, which `cart_at` relies on
+        let carts = map
+            .iter()
+            .filter_map(|((x, y), &(_, dir))| Some(Cart::new(x, y, dir?)))
+            .collect::<Vec<_>>();
+        if carts.is_empty() {
+            return Err(parse::missing(input, "at least one cart"));
+        }
+        Ok(State {
+            carts,
+            track: map.map(|_, &(track_type, _)| track_type),
+        })
+    }
+
+    fn cart_at(carts: &[Cart], x: usize, y: usize) -> Option<usize> {
+        carts
+            .binary_search_by_key(&reading_order((x, y)), |cart| reading_order(cart.pos))
+            .ok()
+    }
+
+    /// Moves every cart once, giving where any crashed, or the position of a
+    /// cart that would leave the track, without moving any.
+    fn tick(&mut self, observer: &mut impl Observer<Event>) -> Result<Vec<Pos>, Pos> {
+        let mut next_carts = Vec::new();
+        let mut collisions = Vec::new();
+        let mut collided_idxs = Vec::new();
+        for (i, cart) in self.carts.iter().enumerate() {
+            if collided_idxs.contains(&i) {
+                continue;
+            }
+            let (x, y) = match cart.next_pos(&self.track) {
+                Some(pos) => pos,
+                None => {
+                    observer.notify(&Event::Derailed { at: cart.pos });
+                    return Err(cart.pos);
+                }
+            };
+            if let Some(collision) = State::cart_at(&next_carts, x, y) {
+                collisions.push((x, y));
+                observer.notify(&Event::Collided { at: (x, y) });
+                next_carts.remove(collision);
+            } else if let Some(collision) = State::cart_at(&self.carts[i + 1..], x, y) {
+                collisions.push((x, y));
+                observer.notify(&Event::Collided { at: (x, y) });
+                collided_idxs.push(collision + i + 1);
+            } else {
+                observer.notify(&Event::Moved {
+                    from: cart.pos,
+                    to: (x, y),
+                });
+                let track_type = self.track[(x, y)];
+                let dir = cart.next_dir(track_type);
+                if dir != cart.dir {
+                    observer.notify(&Event::Turned {
+                        at: (x, y),
+                        from: cart.dir,
+                        to: dir,
+                    });
+                }
+                let intersections = if let TrackType::Intersection = track_type {
+                    cart.intersections + 1
+                } else {
+                    cart.intersections
+                };
+                let i = next_carts
+                    .binary_search_by_key(&reading_order((x, y)), |cart| reading_order(cart.pos))
+                    .unwrap_or_else(|i| i);
+                next_carts.insert(
+                    i,
+                    Cart {
+                        pos: (x, y),
+                        dir,
+                        intersections,
+                    },
+                );
+            }
+        }
+        self.carts = next_carts;
+        Ok(collisions)
+    }
+
+    fn frame(&self, collisions: &[Pos], derailed: Option<Pos>) -> Frame {
+        let mut frame = self.track.map(|_, track_type| match track_type {
+            TrackType::Empty => 0,
+            _ => 1,
+        });
+        for cart in &self.carts {
+            frame[cart.pos] = 2;
+        }
+        for &pos in collisions.iter().chain(&derailed) {
+            frame[pos] = 3;
+        }
+        frame
+    }
+}
+
+/// The carts ticking along until one is left, with the last tick's crashes.
+#[derive(Clone)]
+pub struct Ticks {
+    state: State,
+    collisions: Vec<Pos>,
+    /// The cart that stopped everything by running off the track.
+    derailed: Option<Pos>,
+}
+
+impl Ticks {
+    /// Like [`Simulation::step`], telling `observer` what each cart did.
+    pub fn step_with(&mut self, observer: &mut impl Observer<Event>) -> bool {
+        if self.state.carts.len() <= 1 || self.derailed.is_some() {
+            return false;
+        }
+        match self.state.tick(observer) {
+            Ok(collisions) => self.collisions = collisions,
+            Err(at) => self.derailed = Some(at),
+        }
+        self.derailed.is_none()
+    }
+}
+
+impl Simulation for Ticks {
+    fn step(&mut self) -> bool {
+        self.step_with(&mut ())
+    }
+
+    /// Crashes, and a cart about to leave the track, are drawn in red.
+    fn frame(&self) -> Frame {
+        self.state.frame(&self.collisions, self.derailed)
+    }
+
+    fn status(&self) -> String {
+        let crashes: Vec<_> = self
+            .collisions
+            .iter()
+            .map(|(x, y)| format!("{},{}", x, y))
+            .collect();
+        if let Some((x, y)) = self.derailed {
+            format!("the cart at {},{} left the track", x, y)
+        } else if crashes.is_empty() {
+            format!("{} carts", self.state.carts.len())
+        } else {
+            format!(
+                "{} carts, crashed at {}",
+                self.state.carts.len(),
+                crashes.join(" ")
+            )
+        }
+    }
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u32 = 13;
+    const INPUT: &'static str = include_str!("input.txt");
+    const ANSWERS: &'static str = include_str!("answers.txt");
+
+    type Input = State;
+    type Part1 = Outcome;
+    type Part2 = Outcome;
+
+    fn parse(input: &str) -> parse::Result<State> {
+        State::parse(input)
+    }
+
+    fn part1(state: &State) -> Outcome {
+        if state.carts.len() < 2 {
+            return Outcome::NoCrash;
+        }
+        let mut state = state.clone();
+        loop {
+            match state.tick(&mut ()) {
+                Ok(collisions) if !collisions.is_empty() => return Outcome::At(collisions[0]),
+                Ok(_) => {}
+                Err(at) => return Outcome::Derailed(at),
+            }
+        }
+    }
+
+    fn part2(state: &State) -> Outcome {
+        let mut state = state.clone();
+        while state.carts.len() > 1 {
+            if let Err(at) = state.tick(&mut ()) {
+                return Outcome::Derailed(at);
+            }
+        }
+        match state.carts.first() {
+            Some(cart) => Outcome::At(cart.pos),
+            None => Outcome::NoCartLeft,
+        }
+    }
+}
+
+impl Render for Day13 {
+    const STYLE: Style = Style {
+        palette: &[
+            Rgb(0, 0, 0),
+            Rgb(0x60, 0x60, 0x60),
+            Rgb(0xff, 0xd0, 0),
+            Rgb(0xff, 0x20, 0x20),
+        ],
+        delay: Duration::from_millis(50),
+    };
+
+    fn frames(state: &State) -> Box<dyn Iterator<Item = Frame> + '_> {
+        Box::new(visualize::frames(Self::simulation(state)))
+    }
+}
+
+impl Simulate for Day13 {
+    type Simulation = Ticks;
+
+    fn simulation(state: &State) -> Ticks {
+        Ticks {
+            state: state.clone(),
+            collisions: Vec::new(),
+            derailed: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        let track = r"/->-\        
+|   |  /----\
+| /-+--+-\  |
+| | |  | v  |
+\-+-/  \-+--/
+  \------/   ";
+        assert_eq!(
+            Day13::part1(&Day13::parse(track).unwrap()),
+            Outcome::At((7, 3))
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        let track = r"/>-<\  
+|   |  
+| /<+-\
+| | | v
+\>+</ |
+  |   ^
+  \<->/";
+        assert_eq!(
+            Day13::part2(&Day13::parse(track).unwrap()),
+            Outcome::At((6, 4))
+        );
+    }
+
+    #[test]
+    fn events() {
+        let track = r"/>\
+^ |
+\</";
+        let mut ticks = Day13::simulation(&Day13::parse(track).unwrap());
+        let mut events = Vec::new();
+        ticks.step_with(&mut events);
+        assert_eq!(
+            events,
+            [
+                Event::Moved {
+                    from: (1, 0),
+                    to: (2, 0)
+                },
+                Event::Turned {
+                    at: (2, 0),
+                    from: Direction::Right,
+                    to: Direction::Down
+                },
+                Event::Moved {
+                    from: (0, 1),
+                    to: (0, 0)
+                },
+                Event::Turned {
+                    at: (0, 0),
+                    from: Direction::Up,
+                    to: Direction::Right
+                },
+                Event::Moved {
+                    from: (1, 2),
+                    to: (0, 2)
+                },
+                Event::Turned {
+                    at: (0, 2),
+                    from: Direction::Left,
+                    to: Direction::Up
+                },
+            ]
+        );
+
+        let mut ticks = Day13::simulation(&Day13::parse("-><-").unwrap());
+        let mut events = Vec::new();
+        ticks.step_with(&mut events);
+        assert_eq!(events, [Event::Collided { at: (2, 0) }]);
+    }
+
+    #[test]
+    fn trimmed_input() {
+        let trimmed: Vec<_> = Day13::INPUT.lines().map(str::trim_end).collect();
+        let state = Day13::parse(&trimmed.join("\n")).unwrap();
+        assert_eq!(Day13::part1(&state), Outcome::At((124, 130)));
+        assert_eq!(Day13::part2(&state), Outcome::At((143, 123)));
+    }
+
+    #[test]
+    fn carts_that_cant_finish() {
+        let outcome = |track| Day13::part2(&Day13::parse(track).unwrap());
+        assert_eq!(outcome("-><-"), Outcome::NoCartLeft);
+        assert_eq!(outcome("-<- ->"), Outcome::Derailed((5, 0)));
+        assert_eq!(outcome("->  <-"), Outcome::Derailed((1, 0)));
+        assert_eq!(outcome("-> --<-"), Outcome::Derailed((1, 0)));
+        assert_eq!(
+            Day13::part1(&Day13::parse("->-").unwrap()),
+            Outcome::NoCrash
+        );
+        assert_eq!(
+            Answer::from(outcome("-<>")).to_string(),
+            "a cart left the track at 2,0"
+        );
+
+        let mut ticks = Day13::simulation(&Day13::parse("->  ->").unwrap());
+        let mut events = Vec::new();
+        assert!(!ticks.step_with(&mut events));
+        assert_eq!(events, [Event::Derailed { at: (1, 0) }]);
+        assert!(!ticks.step());
+    }
+
+    #[test]
+    fn no_carts() {
+        let error = Day13::parse("/-\\\n\\-/").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 1: expected at least one cart, found end of input"
+        );
+    }
+}
@@ -0,0 +1,177 @@
+use crate::{answer::Answer, parse, registry::Part, Resume, Solution};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct State {
+    #[serde(with = "digits")]
+    recipes: Vec<u8>,
+    elves: [usize; 2],
+}
+
+/// Saves scores as a string of digits, which is far shorter than a list.
+mod digits {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(digits: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        let text: String = digits.iter().map(|&d| char::from(b'0' + d)).collect();
+        serializer.serialize_str(&text)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        String::deserialize(deserializer)?
+            .chars()
+            .map(|c| {
+                c.to_digit(10)
+                    .map(|d| d as u8)
+                    .ok_or_else(|| de::Error::custom(format!("expected a digit, found `{}`", c)))
+            })
+            .collect()
+    }
+}
+
+impl State {
+    fn new() -> Self {
+        State {
+            recipes: vec![3, 7],
+            elves: [0, 1],
+        }
+    }
+
+    fn run(&mut self) {
+        let sum: u8 = self.elves.iter().map(|&i| self.recipes[i]).sum();
+        if sum >= 10 {
+            self.recipes.push(1);
+            self.recipes.push(sum % 10);
+        } else {
+            self.recipes.push(sum);
+        }
+        for elf in &mut self.elves {
+            *elf += 1 + self.recipes[*elf] as usize;
+            *elf %= self.recipes.len();
+        }
+    }
+
+    /// Where `digits` first appear, if they're among the last recipes made.
+    fn ends_with(&self, digits: &[u8]) -> Option<usize> {
+        self.recipes
+            .windows(digits.len())
+            .enumerate()
+            .rev()
+            .take(2)
+            .find(|&(_, w)| w == digits)
+            .map(|(i, _)| i)
+    }
+
+    fn ten_after(&self, count: usize) -> String {
+        self.recipes[count..count + 10]
+            .iter()
+            .map(|score| score.to_string())
+            .collect()
+    }
+}
+
+/// When the elves can stop making recipes.
+pub enum Goal {
+    /// Once there are ten more than this many.
+    TenAfter(usize),
+    /// Once these scores have been made in a row.
+    Sequence(Vec<u8>),
+}
+
+impl Goal {
+    fn is_met(&self, state: &State) -> bool {
+        match self {
+            Goal::TenAfter(count) => state.recipes.len() >= count + 10,
+            Goal::Sequence(digits) => state.ends_with(digits).is_some(),
+        }
+    }
+}
+
+fn number(digits: &[u8]) -> usize {
+    digits.iter().fold(0, |acc, &d| acc * 10 + d as usize)
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u32 = 14;
+    const INPUT: &'static str = include_str!("input.txt");
+    const ANSWERS: &'static str = include_str!("answers.txt");
+
+    type Input = Vec<u8>;
+    type Part1 = String;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> parse::Result<Vec<u8>> {
+        let mut line = parse::lines(input.trim()).expect("a number of recipes")?;
+        line.cells("a digit", |c| c.to_digit(10).map(|d| d as u8))
+    }
+
+    fn part1(digits: &Vec<u8>) -> String {
+        let count = number(digits);
+        let goal = Goal::TenAfter(count);
+        let mut state = State::new();
+        while !goal.is_met(&state) {
+            state.run();
+        }
+        state.ten_after(count)
+    }
+
+    fn part2(digits: &Vec<u8>) -> usize {
+        let goal = Goal::Sequence(digits.clone());
+        let mut state = State::new();
+        while !goal.is_met(&state) {
+            state.run();
+        }
+        state.ends_with(digits).unwrap()
+    }
+}
+
+impl Resume for Day14 {
+    type State = State;
+    type Context = Goal;
+
+    fn start(digits: &Vec<u8>, part: Part) -> Option<(State, Goal)> {
+        let goal = match part {
+            Part::One => Goal::TenAfter(number(digits)),
+            Part::Two => Goal::Sequence(digits.clone()),
+        };
+        Some((State::new(), goal))
+    }
+
+    fn advance(state: &mut State, goal: &Goal) -> bool {
+        if goal.is_met(state) {
+            return false;
+        }
+        state.run();
+        true
+    }
+
+    fn finish(state: &State, goal: &Goal) -> Answer {
+        match goal {
+            Goal::TenAfter(count) => state.ten_after(*count).into(),
+            Goal::Sequence(digits) => state.ends_with(digits).unwrap().into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_examples() {
+        assert_eq!(Day14::part1(&Day14::parse("9").unwrap()), "5158916779");
+        assert_eq!(Day14::part1(&Day14::parse("5").unwrap()), "0124515891");
+        assert_eq!(Day14::part1(&Day14::parse("18").unwrap()), "9251071085");
+        assert_eq!(Day14::part1(&Day14::parse("2018").unwrap()), "5941429882");
+    }
+
+    #[test]
+    fn part2_examples() {
+        assert_eq!(Day14::part2(&Day14::parse("51589").unwrap()), 9);
+        assert_eq!(Day14::part2(&Day14::parse("01245").unwrap()), 5);
+        assert_eq!(Day14::part2(&Day14::parse("92510").unwrap()), 18);
+        assert_eq!(Day14::part2(&Day14::parse("59414").unwrap()), 2018);
+    }
+}
@@ -0,0 +1,507 @@
+use crate::{
+    grid::{reading_order, Grid, Pos},
+    observe::Observer,
+    parse,
+    visualize::{self, Frame, Rgb, Simulation, Style},
+    Render, Simulate, Solution,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    time::Duration,
+};
+
+#[derive(Clone, Eq, PartialEq)]
+enum Tile {
+    Wall,
+    Floor,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Allegiance {
+    Elf,
+    Goblin,
+}
+
+#[derive(Eq, PartialEq, Clone, Copy)]
+struct Unit {
+    /// Where the unit comes in reading order at the start, which stays the
+    /// same as it moves.
+    id: usize,
+    allegiance: Allegiance,
+    pos: Pos,
+    hp: usize,
+    attack: usize,
+}
+
+impl Unit {
+    fn new(id: usize, allegiance: Allegiance, pos: Pos) -> Self {
+        Unit {
+            id,
+            allegiance,
+            pos,
+            hp: 200,
+            attack: 3,
+        }
+    }
+
+    fn is_alive(&self) -> bool {
+        self.hp > 0
+    }
+}
+
+/// Something a unit did during a round. Units are numbered in reading order
+/// as they stand before combat.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    Moved {
+        unit: usize,
+        from: Pos,
+        to: Pos,
+    },
+    Attacked {
+        attacker: usize,
+        target: usize,
+        damage: usize,
+        /// The target's hit points after the attack.
+        hp: usize,
+    },
+    Died {
+        unit: usize,
+        allegiance: Allegiance,
+        at: Pos,
+    },
+}
+
+#[derive(Clone)]
+pub struct Board {
+    tiles: Grid<Tile>,
+    units: Vec<Unit>,
+    rounds_completed: usize,
+}
+
+impl Board {
+    fn parse(input: &str) -> parse::Result<Self> {
+        let map = Grid::parse(input, "`#`, `.`, `E` or `G`", |c| match c {
+            '#' => Some((Tile::Wall, None)),
+            '.' => Some((Tile::Floor, None)),
+            'E' => Some((Tile::Floor, Some(Allegiance::Elf))),
+            'G' => Some((Tile::Floor, Some(Allegiance::Goblin))),
+            _ => None,
+        })?;
+        let units = map
+            .iter()
+            .filter_map(|(pos, &(_, unit))| Some((pos, unit?)))
+            .enumerate()
+            .map(|(id, (pos, allegiance))| Unit::new(id, allegiance, pos))
+            .collect::<Vec<_>>();
+        if units.is_empty() {
+            return Err(parse::missing(input, "at least one elf or goblin"));
+        }
+        Ok(Board {
+            tiles: map.map(|_, (tile, _)| tile.clone()),
+            units,
+            rounds_completed: 0,
+        })
+    }
+
+    fn with_elf_attack(&self, attack: usize) -> Self {
+        let mut board = self.clone();
+        for unit in &mut board.units {
+            if unit.allegiance == Allegiance::Elf {
+                unit.attack = attack;
+            }
+        }
+        board
+    }
+
+    fn count(&self, allegiance: Allegiance) -> usize {
+        self.units
+            .iter()
+            .filter(|u| u.is_alive() && u.allegiance == allegiance)
+            .count()
+    }
+
+    fn is_open(&self, pos: Pos) -> bool {
+        self.tiles[pos] == Tile::Floor && self.units.iter().all(|u| !u.is_alive() || u.pos != pos)
+    }
+
+    fn adjacent_open_tiles(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.tiles
+            .neighbours4(pos)
+            .filter(move |&pos| self.is_open(pos))
+    }
+
+    /// The living enemy next to unit `i` with the fewest hit points, if any.
+    fn adjacent_enemy(&self, i: usize) -> Option<usize> {
+        let unit = self.units[i];
+        let adjacent: Vec<_> = self.tiles.neighbours4(unit.pos).collect();
+        (0..self.units.len())
+            .filter(|&j| {
+                let other = self.units[j];
+                other.is_alive()
+                    && other.allegiance != unit.allegiance
+                    && adjacent.contains(&other.pos)
+            })
+            .min_by_key(|&j| (self.units[j].hp, reading_order(self.units[j].pos)))
+    }
+
+    /// Where unit `i` steps to get closer to the nearest square in range of an
+    /// enemy, or `None` if it can't reach any.
+    fn next_step(&self, i: usize) -> Option<Pos> {
+        let unit = self.units[i];
+        let from_unit = self.distances(unit.pos);
+        let (_, _, target) = self
+            .units
+            .iter()
+            .filter(|u| u.is_alive() && u.allegiance != unit.allegiance)
+            .flat_map(|u| self.adjacent_open_tiles(u.pos))
+            .filter_map(|pos| {
+                from_unit
+                    .get(&pos)
+                    .map(|&dist| (dist, reading_order(pos), pos))
+            })
+            .min()?;
+        let from_target = self.distances(target);
+        self.adjacent_open_tiles(unit.pos)
+            .filter_map(|pos| {
+                from_target
+                    .get(&pos)
+                    .map(|&dist| (dist, reading_order(pos), pos))
+            })
+            .min()
+            .map(|(_, _, pos)| pos)
+    }
+
+    /// Breadth-first distances through open tiles from `start`.
+    fn distances(&self, start: Pos) -> HashMap<Pos, usize> {
+        let mut distances = HashMap::new();
+        let mut queue = VecDeque::new();
+        distances.insert(start, 0);
+        queue.push_back(start);
+        while let Some(pos) = queue.pop_front() {
+            let dist = distances[&pos];
+            for next in self.adjacent_open_tiles(pos) {
+                distances.entry(next).or_insert_with(|| {
+                    queue.push_back(next);
+                    dist + 1
+                });
+            }
+        }
+        distances
+    }
+
+    /// Plays out one round, returning `false` if combat ended partway through.
+    fn round(&mut self, observer: &mut impl Observer<Event>) -> bool {
+        self.units.sort_by_key(|u| reading_order(u.pos));
+        for i in 0..self.units.len() {
+            let unit = self.units[i];
+            if !unit.is_alive() {
+                continue;
+            }
+            if self
+                .units
+                .iter()
+                .all(|u| !u.is_alive() || u.allegiance == unit.allegiance)
+            {
+                self.units.retain(Unit::is_alive);
+                return false;
+            }
+            if self.adjacent_enemy(i).is_none() {
+                if let Some(pos) = self.next_step(i) {
+                    self.units[i].pos = pos;
+                    observer.notify(&Event::Moved {
+                        unit: unit.id,
+                        from: unit.pos,
+                        to: pos,
+                    });
+                }
+            }
+            if let Some(j) = self.adjacent_enemy(i) {
+                let enemy = &mut self.units[j];
+                enemy.hp = enemy.hp.saturating_sub(unit.attack);
+                observer.notify(&Event::Attacked {
+                    attacker: unit.id,
+                    target: enemy.id,
+                    damage: unit.attack,
+                    hp: enemy.hp,
+                });
+                if !enemy.is_alive() {
+                    observer.notify(&Event::Died {
+                        unit: enemy.id,
+                        allegiance: enemy.allegiance,
+                        at: enemy.pos,
+                    });
+                }
+            }
+        }
+        self.units.retain(Unit::is_alive);
+        self.rounds_completed += 1;
+        true
+    }
+
+    fn outcome(&self) -> usize {
+        self.rounds_completed * self.units.iter().map(|u| u.hp).sum::<usize>()
+    }
+
+    fn frame(&self) -> Frame {
+        let mut frame = self.tiles.map(|_, tile| match tile {
+            Tile::Wall => 0,
+            Tile::Floor => 1,
+        });
+        for unit in self.units.iter().filter(|u| u.is_alive()) {
+            frame[unit.pos] = match unit.allegiance {
+                Allegiance::Elf => 2,
+                Allegiance::Goblin => 3,
+            };
+        }
+        frame
+    }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let map = self
+            .tiles
+            .map(|pos, tile| match self.units.iter().find(|u| u.pos == pos) {
+                Some(unit) if unit.allegiance == Allegiance::Elf => 'E',
+                Some(_) => 'G',
+                None if *tile == Tile::Wall => '#',
+                None => '.',
+            });
+        map.fmt(f)
+    }
+}
+
+/// Part 1's combat, a round at a time.
+#[derive(Clone)]
+pub struct Combat {
+    board: Board,
+    over: bool,
+}
+
+impl Combat {
+    /// Like [`Simulation::step`], telling `observer` what each unit did.
+    pub fn step_with(&mut self, observer: &mut impl Observer<Event>) -> bool {
+        if self.over {
+            return false;
+        }
+        self.over = !self.board.round(observer);
+        true
+    }
+}
+
+impl Simulation for Combat {
+    fn step(&mut self) -> bool {
+        self.step_with(&mut ())
+    }
+
+    fn frame(&self) -> Frame {
+        self.board.frame()
+    }
+
+    fn status(&self) -> String {
+        let status = format!(
+            "{} rounds, {} elves, {} goblins",
+            self.board.rounds_completed,
+            self.board.count(Allegiance::Elf),
+            self.board.count(Allegiance::Goblin)
+        );
+        if self.over {
+            format!("{}, outcome {}", status, self.board.outcome())
+        } else {
+            status
+        }
+    }
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u32 = 15;
+    const INPUT: &'static str = include_str!("input.txt");
+    const ANSWERS: &'static str = include_str!("answers.txt");
+
+    type Input = Board;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> parse::Result<Board> {
+        Board::parse(input)
+    }
+
+    fn part1(board: &Board) -> usize {
+        let mut board = board.clone();
+        while board.round(&mut ()) {}
+        board.outcome()
+    }
+
+    fn part2(board: &Board) -> usize {
+        let elves = board.count(Allegiance::Elf);
+        (4..)
+            .find_map(|attack| {
+                let mut board = board.with_elf_attack(attack);
+                while board.round(&mut ()) {
+                    if board.count(Allegiance::Elf) < elves {
+                        return None;
+                    }
+                }
+                if board.count(Allegiance::Elf) == elves {
+                    Some(board.outcome())
+                } else {
+                    None
+                }
+            })
+            .unwrap()
+    }
+}
+
+impl Render for Day15 {
+    const STYLE: Style = Style {
+        palette: &[
+            Rgb(0x30, 0x30, 0x30),
+            Rgb(0xc8, 0xb4, 0x8c),
+            Rgb(0x20, 0xa0, 0x40),
+            Rgb(0xc0, 0x20, 0x20),
+        ],
+        delay: Duration::from_millis(200),
+    };
+
+    /// The board before combat, then after each round of part 1.
+    fn frames(board: &Board) -> Box<dyn Iterator<Item = Frame> + '_> {
+        Box::new(visualize::frames(Self::simulation(board)))
+    }
+}
+
+impl Simulate for Day15 {
+    type Simulation = Combat;
+
+    fn simulation(board: &Board) -> Combat {
+        Combat {
+            board: board.clone(),
+            over: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLES: [(&str, usize, Option<usize>); 6] = [
+        (
+            "#######\n#.G...#\n#...EG#\n#.#.#G#\n#..G#E#\n#.....#\n#######",
+            27730,
+            Some(4988),
+        ),
+        (
+            "#######\n#G..#E#\n#E#E.E#\n#G.##.#\n#...#E#\n#...E.#\n#######",
+            36334,
+            None,
+        ),
+        (
+            "#######\n#E..EG#\n#.#G.E#\n#E.##E#\n#G..#.#\n#..E#.#\n#######",
+            39514,
+            Some(31284),
+        ),
+        (
+            "#######\n#E.G#.#\n#.#G..#\n#G.#.G#\n#G..#.#\n#...E.#\n#######",
+            27755,
+            Some(3478),
+        ),
+        (
+            "#######\n#.E...#\n#.#..G#\n#.###.#\n#E#G#G#\n#...#G#\n#######",
+            28944,
+            Some(6474),
+        ),
+        (
+            "#########\n#G......#\n#.E.#...#\n#..##..G#\n#...##..#\n#...#...#\n#.G...G.#\n#.....G.#\n#########",
+            18740,
+            Some(1140),
+        ),
+    ];
+
+    #[test]
+    fn part1_examples() {
+        for &(map, outcome, _) in &EXAMPLES {
+            assert_eq!(
+                Day15::part1(&Day15::parse(map).unwrap()),
+                outcome,
+                "\n{}",
+                map
+            );
+        }
+    }
+
+    #[test]
+    fn part2_examples() {
+        for &(map, _, outcome) in &EXAMPLES {
+            if let Some(outcome) = outcome {
+                assert_eq!(
+                    Day15::part2(&Day15::parse(map).unwrap()),
+                    outcome,
+                    "\n{}",
+                    map
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn events() {
+        let board = Day15::parse("#######\n#E..G.#\n#######").unwrap();
+        let mut combat = Day15::simulation(&board.with_elf_attack(200));
+        let mut events = Vec::new();
+        while combat.step_with(&mut events) {}
+        assert_eq!(
+            events,
+            [
+                Event::Moved {
+                    unit: 0,
+                    from: (1, 1),
+                    to: (2, 1)
+                },
+                Event::Moved {
+                    unit: 1,
+                    from: (4, 1),
+                    to: (3, 1)
+                },
+                Event::Attacked {
+                    attacker: 1,
+                    target: 0,
+                    damage: 3,
+                    hp: 197
+                },
+                Event::Attacked {
+                    attacker: 0,
+                    target: 1,
+                    damage: 200,
+                    hp: 0
+                },
+                Event::Died {
+                    unit: 1,
+                    allegiance: Allegiance::Goblin,
+                    at: (3, 1)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_tile() {
+        let error = Day15::parse("#####\n#E.x#\n#####").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 4: expected `#`, `.`, `E` or `G`, found `x`"
+        );
+    }
+
+    #[test]
+    fn no_units() {
+        let error = Day15::parse("#####\n#...#\n#####").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 4, column 1: expected at least one elf or goblin, found end of input"
+        );
+    }
+}
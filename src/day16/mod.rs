@@ -0,0 +1,230 @@
+use crate::{
+    answer::{Answer, Fact},
+    elfcode::{Cpu, Inst, Opcode, Program, RawInst},
+    iter::IteratorExt,
+    parse::{self, Line},
+    registry::Part,
+    Solution,
+};
+use std::collections::{hash_map::Entry, HashMap, HashSet};
+
+type Registers = [usize; 4];
+
+pub struct Sample {
+    before: Registers,
+    after: Registers,
+    inst: RawInst,
+}
+
+impl Sample {
+    fn opcode_candidates(&self) -> HashSet<Opcode> {
+        Opcode::ALL
+            .iter()
+            .cloned()
+            .filter(|&opcode| {
+                let inst = Inst::new(opcode, self.inst.a, self.inst.b, self.inst.c);
+                let mut test = self.before;
+                inst.exec(&mut test);
+                test == self.after
+            })
+            .collect()
+    }
+}
+
+/// Parses registers like `[3, 2, 1, 1]`.
+fn registers(line: &mut Line) -> parse::Result<Registers> {
+    let mut regs = [0; 4];
+    line.literal("[")?;
+    for (i, reg) in regs.iter_mut().enumerate() {
+        if i > 0 {
+            line.literal(",")?;
+        }
+        *reg = line.number()?;
+    }
+    line.literal("]")?;
+    Ok(regs)
+}
+
+fn map_opcodes(samples: &[Sample]) -> HashMap<usize, Opcode> {
+    let mut candidates: HashMap<usize, HashSet<Opcode>> = HashMap::new();
+    for sample in samples {
+        let sample_candidates = sample.opcode_candidates();
+        match candidates.entry(sample.inst.opcode) {
+            Entry::Occupied(mut entry) => {
+                entry
+                    .get_mut()
+                    .retain(|opcode| sample_candidates.contains(opcode));
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(sample_candidates);
+            }
+        }
+    }
+    let mut mapping = HashMap::new();
+    let mut mapped = HashSet::new();
+    // each pass maps whatever numbers are down to one opcode, until a pass
+    // maps nothing new
+    loop {
+        let before = mapping.len();
+        for (&n, candidates) in &candidates {
+            if mapping.contains_key(&n) {
+                continue;
+            }
+            if let Some(&opcode) = candidates.difference(&mapped).single() {
+                mapping.insert(n, opcode);
+                mapped.insert(opcode);
+            }
+        }
+        if mapping.len() == before {
+            return mapping;
+        }
+    }
+}
+
+pub struct Manual {
+    samples: Vec<Sample>,
+    program: Vec<RawInst>,
+}
+
+/// Register 0 after running the program, or the first opcode number in it
+/// that the samples don't pin down.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Register0(pub Result<usize, usize>);
+
+impl From<Register0> for Answer {
+    fn from(register: Register0) -> Self {
+        match register.0 {
+            Ok(value) => value.into(),
+            Err(n) => Answer::String(format!("opcode {} is never identified", n)),
+        }
+    }
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u32 = 16;
+    const INPUT: &'static str = include_str!("input.txt");
+    const ANSWERS: &'static str = include_str!("answers.txt");
+
+    type Input = Manual;
+    type Part1 = usize;
+    type Part2 = Register0;
+
+    fn parse(input: &str) -> parse::Result<Manual> {
+        let mut lines = parse::lines(input);
+        let mut samples = Vec::new();
+        let mut program = Vec::new();
+        while let Some(mut line) = lines.next() {
+            if line.is_empty() {
+                continue;
+            }
+            if !line.rest().starts_with("Before:") {
+                program.push(RawInst::parse(line)?);
+                continue;
+            }
+            line.literal("Before:")?;
+            let before = registers(&mut line)?;
+            line.end()?;
+            let inst = RawInst::parse(lines.expect("an instruction")?)?;
+            let mut line = lines.expect("`After:`")?;
+            line.literal("After:")?;
+            let after = registers(&mut line)?;
+            line.end()?;
+            samples.push(Sample {
+                before,
+                after,
+                inst,
+            });
+        }
+        Ok(Manual { samples, program })
+    }
+
+    fn part1(manual: &Manual) -> usize {
+        manual
+            .samples
+            .iter()
+            .filter(|s| s.opcode_candidates().len() >= 3)
+            .count()
+    }
+
+    fn part2(manual: &Manual) -> Register0 {
+        Register0(run_program(manual, &map_opcodes(&manual.samples)).map(|cpu| cpu.registers[0]))
+    }
+
+    fn explain(manual: &Manual, part: Part) -> Vec<Fact> {
+        match part {
+            Part::One => vec![Fact::new("samples", manual.samples.len())],
+            Part::Two => {
+                let map = map_opcodes(&manual.samples);
+                let mut numbers: Vec<_> = map.keys().copied().collect();
+                numbers.sort_unstable();
+                let mut facts: Vec<_> = numbers
+                    .into_iter()
+                    .map(|n| Fact::new(format!("opcode {}", n), map[&n].to_string()))
+                    .collect();
+                if let Ok(cpu) = run_program(manual, &map) {
+                    let registers: Vec<_> = cpu.registers.iter().map(|&r| r as i64).collect();
+                    facts.push(Fact::new("registers", Answer::Coord(registers)));
+                }
+                facts
+            }
+        }
+    }
+}
+
+/// Runs the manual's program with `map`, or returns the first opcode number
+/// in it that `map` lacks.
+fn run_program(manual: &Manual, map: &HashMap<usize, Opcode>) -> Result<Cpu<4>, usize> {
+    let program = Program::load(&manual.program, map).ok_or_else(|| {
+        let unmapped = manual
+            .program
+            .iter()
+            .find(|inst| !map.contains_key(&inst.opcode));
+        unmapped
+            .expect("`load` only fails on an unmapped opcode")
+            .opcode
+    })?;
+    let mut cpu = Cpu::<4>::new([0; 4]);
+    cpu.run(&program);
+    Ok(cpu)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_example() {
+        let sample = Sample {
+            before: [3, 2, 1, 1],
+            after: [3, 2, 2, 1],
+            inst: RawInst {
+                opcode: 9,
+                a: 2,
+                b: 1,
+                c: 2,
+            },
+        };
+        let expected = [Opcode::Mulr, Opcode::Addi, Opcode::Seti];
+        assert_eq!(
+            sample.opcode_candidates(),
+            expected.iter().cloned().collect()
+        );
+    }
+
+    #[test]
+    fn unmapped_opcodes() {
+        let manual = Day16::parse("1 0 0 0").unwrap();
+        assert_eq!(Day16::part2(&manual), Register0(Err(1)));
+
+        // opcode 0 could be any of several, so it's never mapped
+        let ambiguous = "Before: [0, 0, 0, 0]\n0 0 0 0\nAfter:  [0, 0, 0, 0]\n\n0 1 2 3";
+        let manual = Day16::parse(ambiguous).unwrap();
+        assert_eq!(
+            Answer::from(Day16::part2(&manual)).to_string(),
+            "opcode 0 is never identified"
+        );
+        assert!(Day16::explain(&manual, Part::Two).is_empty());
+    }
+}
@@ -0,0 +1,398 @@
+use crate::{
+    grid::{Grid, Pos},
+    observe::Observer,
+    parse,
+    visualize::{self, Frame, Rgb, Simulation, Style},
+    Render, Simulate, Solution,
+};
+use itertools::{iproduct, Itertools};
+use std::{
+    collections::{HashSet, VecDeque},
+    time::Duration,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Sand,
+    Clay,
+    FlowingWater,
+    RestingWater,
+}
+
+impl Tile {
+    fn is_passable(&self) -> bool {
+        match *self {
+            Tile::Sand => true,
+            Tile::Clay => false,
+            Tile::FlowingWater => true,
+            Tile::RestingWater => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum FlowTask {
+    FlowDown(usize, usize),
+    FillOrSpill(usize, usize),
+}
+
+impl FlowTask {
+    fn pos(&self) -> (usize, usize) {
+        match *self {
+            FlowTask::FlowDown(x, y) => (x, y),
+            FlowTask::FillOrSpill(x, y) => (x, y),
+        }
+    }
+}
+
+/// Something that happened as the water flowed, in the scan's coordinates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    Queued(FlowTask),
+    /// Water flowed into or came to rest on a tile.
+    Filled {
+        x: usize,
+        y: usize,
+        tile: Tile,
+    },
+}
+
+enum FlowEnd {
+    Wall(usize),
+    Edge(usize),
+}
+
+#[derive(Clone)]
+pub struct State {
+    /// The scanned area, from one column left of the leftmost clay to one
+    /// right of the rightmost, and from the topmost clay to the bottommost.
+    tiles: Grid<Tile>,
+    /// Where the top left of `tiles` is in the scan's coordinates.
+    origin: Pos,
+    flowtasks: VecDeque<FlowTask>,
+    seen: HashSet<FlowTask>,
+}
+
+impl State {
+    fn initial(clay_positions: &[(usize, usize)]) -> Self {
+        let (xmin, xmax) = clay_positions
+            .iter()
+            .map(|&(x, _)| x)
+            .minmax()
+            .into_option()
+            .unwrap();
+        let (ymin, ymax) = clay_positions
+            .iter()
+            .map(|&(_, y)| y)
+            .minmax()
+            .into_option()
+            .unwrap();
+        let origin = (xmin - 1, ymin);
+        let mut tiles = Grid::new(xmax - xmin + 3, ymax - ymin + 1, Tile::Sand);
+        for &(x, y) in clay_positions {
+            tiles[(x - origin.0, y - origin.1)] = Tile::Clay;
+        }
+        let mut flowtasks = VecDeque::new();
+        flowtasks.push_back(FlowTask::FlowDown(500, ymin));
+        State {
+            tiles,
+            origin,
+            flowtasks,
+            seen: HashSet::new(),
+        }
+    }
+
+    /// Where `(x, y)` is in `tiles`, if it's in the scanned area.
+    fn local(&self, x: usize, y: usize) -> Option<Pos> {
+        let pos = (x.checked_sub(self.origin.0)?, y.checked_sub(self.origin.1)?);
+        if self.tiles.contains(pos) {
+            Some(pos)
+        } else {
+            None
+        }
+    }
+
+    fn at(&self, x: usize, y: usize) -> Tile {
+        self.local(x, y).map_or(Tile::Sand, |pos| self.tiles[pos])
+    }
+
+    fn insert(&mut self, x: usize, y: usize, tile: Tile, observer: &mut impl Observer<Event>) {
+        if let Some(pos) = self.local(x, y) {
+            if self.tiles[pos] != tile {
+                self.tiles[pos] = tile;
+                observer.notify(&Event::Filled { x, y, tile });
+            }
+        }
+    }
+
+    fn inbounds(&self, x: usize, y: usize) -> bool {
+        self.local(x, y).is_some()
+    }
+
+    fn task_inbounds(&self, task: &FlowTask) -> bool {
+        let (x, y) = task.pos();
+        self.inbounds(x, y)
+    }
+
+    fn run(&mut self) {
+        while self.step() {}
+    }
+
+    fn perform_task(
+        &mut self,
+        task: FlowTask,
+        observer: &mut impl Observer<Event>,
+    ) -> Vec<FlowTask> {
+        let mut next = Vec::new();
+        match task {
+            FlowTask::FlowDown(x, mut y) => {
+                while self.inbounds(x, y) && self.at(x, y).is_passable() {
+                    self.insert(x, y, Tile::FlowingWater, observer);
+                    y += 1;
+                }
+                if self.inbounds(x, y) {
+                    next.push(FlowTask::FillOrSpill(x, y - 1));
+                }
+            }
+            FlowTask::FillOrSpill(x, y) => match self.flow_ends(x, y) {
+                (FlowEnd::Wall(left), FlowEnd::Wall(right)) => {
+                    for x in left + 1..right {
+                        self.insert(x, y, Tile::RestingWater, observer);
+                    }
+                    next.push(FlowTask::FillOrSpill(x, y - 1));
+                }
+                (FlowEnd::Wall(left), FlowEnd::Edge(right)) => {
+                    for x in left + 1..=right {
+                        self.insert(x, y, Tile::FlowingWater, observer);
+                    }
+                    if self.at(right, y + 1) != Tile::FlowingWater {
+                        next.push(FlowTask::FlowDown(right, y + 1));
+                    }
+                }
+                (FlowEnd::Edge(left), FlowEnd::Wall(right)) => {
+                    for x in left..right {
+                        self.insert(x, y, Tile::FlowingWater, observer);
+                    }
+                    if self.at(left, y + 1) != Tile::FlowingWater {
+                        next.push(FlowTask::FlowDown(left, y + 1));
+                    }
+                }
+                (FlowEnd::Edge(left), FlowEnd::Edge(right)) => {
+                    for x in left..=right {
+                        self.insert(x, y, Tile::FlowingWater, observer);
+                    }
+                    if self.at(right, y + 1) != Tile::FlowingWater {
+                        next.push(FlowTask::FlowDown(right, y + 1));
+                    }
+                    if self.at(left, y + 1) != Tile::FlowingWater {
+                        next.push(FlowTask::FlowDown(left, y + 1));
+                    }
+                }
+            },
+        };
+        next
+    }
+
+    fn flow_ends(&self, x: usize, y: usize) -> (FlowEnd, FlowEnd) {
+        let (mut left, mut right) = (x - 1, x + 1);
+        let left = loop {
+            if !self.at(left, y).is_passable() {
+                break FlowEnd::Wall(left);
+            } else if self.at(left, y + 1).is_passable() {
+                break FlowEnd::Edge(left);
+            } else {
+                left -= 1;
+            }
+        };
+        let right = loop {
+            if !self.at(right, y).is_passable() {
+                break FlowEnd::Wall(right);
+            } else if self.at(right, y + 1).is_passable() {
+                break FlowEnd::Edge(right);
+            } else {
+                right += 1;
+            }
+        };
+        (left, right)
+    }
+
+    fn watered_tiles(&self) -> usize {
+        self.tiles
+            .cells()
+            .filter(|&&tile| tile == Tile::FlowingWater || tile == Tile::RestingWater)
+            .count()
+    }
+
+    fn resting_water(&self) -> usize {
+        self.tiles
+            .cells()
+            .filter(|&&tile| tile == Tile::RestingWater)
+            .count()
+    }
+}
+
+impl State {
+    /// Performs the next task that hasn't been done yet, telling `observer`
+    /// which tiles it filled and which tasks it queued, and returning
+    /// `false` if there are none left.
+    pub fn step_with(&mut self, observer: &mut impl Observer<Event>) -> bool {
+        while let Some(task) = self.flowtasks.pop_front() {
+            if self.task_inbounds(&task) && !self.seen.contains(&task) {
+                self.seen.insert(task);
+                for next in self.perform_task(task, observer) {
+                    observer.notify(&Event::Queued(next));
+                    self.flowtasks.push_back(next);
+                }
+                return true;
+            }
+        }
+        false
+    }
+}
+
+impl Simulation for State {
+    fn step(&mut self) -> bool {
+        self.step_with(&mut ())
+    }
+
+    fn frame(&self) -> Frame {
+        self.tiles.map(|_, tile| match tile {
+            Tile::Sand => 0,
+            Tile::Clay => 1,
+            Tile::FlowingWater => 2,
+            Tile::RestingWater => 3,
+        })
+    }
+
+    fn status(&self) -> String {
+        format!(
+            "{} tasks queued, {} tiles watered, {} resting",
+            self.flowtasks.len(),
+            self.watered_tiles(),
+            self.resting_water()
+        )
+    }
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u32 = 17;
+    const INPUT: &'static str = include_str!("input.txt");
+    const ANSWERS: &'static str = include_str!("answers.txt");
+
+    type Input = Vec<(usize, usize)>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> parse::Result<Vec<(usize, usize)>> {
+        let mut clay_positions = Vec::new();
+        for mut line in parse::lines(input).at_least_one("at least one vein of clay")? {
+            // either a vertical vein like `x=495, y=2..7` or a horizontal
+            // one like `y=7, x=495..501`
+            let vertical = match line.peek() {
+                Some('x') => true,
+                Some('y') => false,
+                _ => return Err(line.error("`x=` or `y=`")),
+            };
+            let (fixed, range) = if vertical { ("x=", "y=") } else { ("y=", "x=") };
+            line.literal(fixed)?;
+            let at = line.number()?;
+            line.literal(",")?;
+            line.literal(range)?;
+            let min = line.number()?;
+            line.literal("..")?;
+            let max = line.number()?;
+            line.end()?;
+            if vertical {
+                clay_positions.extend(iproduct!(at..=at, min..=max));
+            } else {
+                clay_positions.extend(iproduct!(min..=max, at..=at));
+            }
+        }
+        Ok(clay_positions)
+    }
+
+    fn part1(clay_positions: &Vec<(usize, usize)>) -> usize {
+        let mut state = State::initial(clay_positions);
+        state.run();
+        state.watered_tiles()
+    }
+
+    fn part2(clay_positions: &Vec<(usize, usize)>) -> usize {
+        let mut state = State::initial(clay_positions);
+        state.run();
+        state.resting_water()
+    }
+}
+
+impl Render for Day17 {
+    const STYLE: Style = Style {
+        palette: &[
+            Rgb(0, 0, 0),
+            Rgb(0xff, 0xff, 0xff),
+            Rgb(0x40, 0x80, 0xff),
+            Rgb(0, 0, 0xff),
+        ],
+        delay: Duration::from_millis(20),
+    };
+
+    /// The scan before any water flows, then after each task.
+    fn frames(clay_positions: &Vec<(usize, usize)>) -> Box<dyn Iterator<Item = Frame> + '_> {
+        Box::new(visualize::frames(Self::simulation(clay_positions)))
+    }
+}
+
+impl Simulate for Day17 {
+    type Simulation = State;
+
+    fn simulation(clay_positions: &Vec<(usize, usize)>) -> State {
+        State::initial(clay_positions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+x=495, y=2..7
+y=7, x=495..501
+x=501, y=3..7
+x=498, y=2..4
+x=506, y=1..2
+x=498, y=10..13
+x=504, y=10..13
+y=13, x=498..504";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day17::part1(&Day17::parse(EXAMPLE).unwrap()), 57);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day17::part2(&Day17::parse(EXAMPLE).unwrap()), 29);
+    }
+
+    #[test]
+    fn events() {
+        let basin = "x=499, y=1..2\nx=501, y=1..2\ny=3, x=499..501";
+        let mut state = Day17::simulation(&Day17::parse(basin).unwrap());
+        let mut events = Vec::new();
+        while state.step_with(&mut events) {}
+        let filled = |x, y, tile| Event::Filled { x, y, tile };
+        assert_eq!(
+            events,
+            [
+                filled(500, 1, Tile::FlowingWater),
+                filled(500, 2, Tile::FlowingWater),
+                Event::Queued(FlowTask::FillOrSpill(500, 2)),
+                filled(500, 2, Tile::RestingWater),
+                Event::Queued(FlowTask::FillOrSpill(500, 1)),
+                filled(500, 1, Tile::RestingWater),
+                Event::Queued(FlowTask::FillOrSpill(500, 0)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,286 @@
+use crate::{
+    answer::Answer,
+    cycle,
+    grid::{Grid, Pos},
+    params::{Param, Params},
+    parse,
+    registry::Part,
+    visualize::{self, Frame, Rgb, Simulation, Style},
+    Render, Resume, Simulate, Solution,
+};
+use serde::{Deserialize, Serialize};
+use std::{fmt, time::Duration};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+enum Acre {
+    Ground,
+    Trees,
+    Lumberyard,
+}
+
+impl fmt::Display for Acre {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Acre::Ground => '.'.fmt(f),
+            Acre::Trees => '|'.fmt(f),
+            Acre::Lumberyard => '#'.fmt(f),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct State(Grid<Acre>);
+
+struct Counts {
+    trees: usize,
+    lumberyard: usize,
+}
+
+impl State {
+    fn adjacent(&self, pos: Pos) -> Counts {
+        let (mut trees, mut lumberyard) = (0, 0);
+        for neighbour in self.0.neighbours8(pos) {
+            match self.0[neighbour] {
+                Acre::Ground => {}
+                Acre::Trees => trees += 1,
+                Acre::Lumberyard => lumberyard += 1,
+            };
+        }
+        Counts { trees, lumberyard }
+    }
+
+    fn next_acre(&self, pos: Pos) -> Acre {
+        let adjacent = self.adjacent(pos);
+        match self.0[pos] {
+            Acre::Ground => {
+                if adjacent.trees >= 3 {
+                    Acre::Trees
+                } else {
+                    Acre::Ground
+                }
+            }
+            Acre::Trees => {
+                if adjacent.lumberyard >= 3 {
+                    Acre::Lumberyard
+                } else {
+                    Acre::Trees
+                }
+            }
+            Acre::Lumberyard => {
+                if adjacent.lumberyard >= 1 && adjacent.trees >= 1 {
+                    Acre::Lumberyard
+                } else {
+                    Acre::Ground
+                }
+            }
+        }
+    }
+
+    fn next_state(&self) -> State {
+        State(self.0.map(|pos, _| self.next_acre(pos)))
+    }
+
+    fn resource_value(&self) -> usize {
+        let (mut trees, mut lumberyard) = (0, 0);
+        for acre in self.0.cells() {
+            match acre {
+                Acre::Trees => trees += 1,
+                Acre::Lumberyard => lumberyard += 1,
+                Acre::Ground => {}
+            }
+        }
+        trees * lumberyard
+    }
+}
+
+impl Simulation for State {
+    /// A minute passes; the forest never stops changing.
+    fn step(&mut self) -> bool {
+        *self = self.next_state();
+        true
+    }
+
+    fn frame(&self) -> Frame {
+        self.0.map(|_, acre| match acre {
+            Acre::Ground => 0,
+            Acre::Trees => 1,
+            Acre::Lumberyard => 2,
+        })
+    }
+
+    fn status(&self) -> String {
+        format!("resource value {}", self.resource_value())
+    }
+}
+
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+const MINUTES: Param = Param {
+    name: "minutes",
+    doc: "How many minutes part 1 lets the forest change for",
+    default: 10,
+    min: 0,
+    max: u64::MAX,
+};
+
+const LONG_MINUTES: Param = Param {
+    name: "long_minutes",
+    doc: "How many minutes part 2 lets the forest change for",
+    default: 1_000_000_000,
+    min: 0,
+    max: u64::MAX,
+};
+
+pub struct Forest {
+    initial_state: State,
+    minutes: usize,
+    long_minutes: usize,
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u32 = 18;
+    const INPUT: &'static str = include_str!("input.txt");
+    const ANSWERS: &'static str = include_str!("answers.txt");
+
+    const PARAMS: &'static [Param] = &[MINUTES, LONG_MINUTES];
+
+    type Input = Forest;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> parse::Result<Forest> {
+        let acres = Grid::parse(input, "`.`, `|` or `#`", |c| match c {
+            '.' => Some(Acre::Ground),
+            '|' => Some(Acre::Trees),
+            '#' => Some(Acre::Lumberyard),
+            _ => None,
+        })?;
+        Ok(Forest {
+            initial_state: State(acres),
+            minutes: MINUTES.default as usize,
+            long_minutes: LONG_MINUTES.default as usize,
+        })
+    }
+
+    fn configure(forest: &mut Forest, params: &Params) {
+        forest.minutes = params.get(&MINUTES) as usize;
+        forest.long_minutes = params.get(&LONG_MINUTES) as usize;
+    }
+
+    fn part1(forest: &Forest) -> usize {
+        let mut state = forest.initial_state.clone();
+        for _ in 0..forest.minutes {
+            state = state.next_state();
+        }
+        state.resource_value()
+    }
+
+    fn part2(forest: &Forest) -> usize {
+        // thanks again jewel!!!!
+        let state = cycle::fast_forward(
+            forest.initial_state.clone(),
+            forest.long_minutes,
+            |state| *state = state.next_state(),
+            |state| state.0.clone(),
+        );
+        state.resource_value()
+    }
+}
+
+impl Render for Day18 {
+    // also jewel's idea~
+    const STYLE: Style = Style {
+        palette: &[Rgb(0, 0, 0), Rgb(0x0b, 0x66, 0x23), Rgb(0x8b, 0x45, 0x13)],
+        delay: Duration::from_millis(50),
+    };
+
+    fn frames(forest: &Forest) -> Box<dyn Iterator<Item = Frame> + '_> {
+        Box::new(visualize::frames(Self::simulation(forest)).take(2000))
+    }
+}
+
+impl Simulate for Day18 {
+    type Simulation = State;
+
+    fn simulation(forest: &Forest) -> State {
+        forest.initial_state.clone()
+    }
+}
+
+/// The forest some minutes in.
+#[derive(Serialize, Deserialize)]
+pub struct Growth {
+    state: State,
+    minute: usize,
+}
+
+impl Resume for Day18 {
+    type State = Growth;
+    /// How many minutes to let pass.
+    type Context = usize;
+
+    /// Part 2 skips ahead once the forest starts repeating, so it never takes
+    /// long enough to need resuming.
+    fn start(forest: &Forest, part: Part) -> Option<(Growth, usize)> {
+        match part {
+            Part::One => Some((
+                Growth {
+                    state: forest.initial_state.clone(),
+                    minute: 0,
+                },
+                forest.minutes,
+            )),
+            Part::Two => None,
+        }
+    }
+
+    fn advance(growth: &mut Growth, &minutes: &usize) -> bool {
+        if growth.minute >= minutes {
+            return false;
+        }
+        growth.state = growth.state.next_state();
+        growth.minute += 1;
+        true
+    }
+
+    fn finish(growth: &Growth, _: &usize) -> Answer {
+        growth.state.resource_value().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+.#.#...|#.
+.....#|##|
+.|..|...#.
+..|#.....#
+#.#|||#|#|
+...#.||...
+.|....|...
+||...#|.#|
+|.||||..|.
+...#.|..|.";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day18::part1(&Day18::parse(EXAMPLE).unwrap()), 1147);
+    }
+
+    #[test]
+    fn unknown_acre() {
+        let error = Day18::parse(".#.\n.|?").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: expected `.`, `|` or `#`, found `?`"
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use crate::{
+    answer::Answer,
+    elfcode::{Cpu, Program},
+    parse,
+    registry::Part,
+    Resume, Solution,
+};
+
+/// The sum of `n`'s divisors.
+fn divisor_sum(n: usize) -> usize {
+    (1..)
+        .take_while(|&d| d * d <= n)
+        .filter(|&d| n.is_multiple_of(d))
+        .map(|d| if d * d == n { d } else { d + n / d })
+        .sum()
+}
+
+/// Whether `cpu` just jumped back to instruction 1 from further on.
+fn reached_loop(from: usize, cpu: &Cpu<6>) -> bool {
+    cpu.ip == 1 && from > 1
+}
+
+/// What the program leaves in register 0 if it carries on from `cpu`, worked
+/// out without running the loop it's about to start, or `None` if that loop
+/// doesn't just add up the divisors of a number.
+///
+/// Day 19 programs jump to a routine at the end that builds a number in one
+/// of the registers, then jump back to instruction 1 to add that number's
+/// divisors to register 0 by trying every pair of numbers up to it. Which
+/// register it's in, and whether the loop really does that, is checked by
+/// running it with a few small numbers in place of the big one.
+pub fn skip_loop(program: &Program<6>, cpu: &Cpu<6>) -> Option<usize> {
+    if cpu.ip != 1 {
+        return None;
+    }
+    let sums_divisors = |register: usize| {
+        [6, 10, 12, 28].iter().all(|&n| {
+            let mut test = *cpu;
+            test.registers[register] = n;
+            let finished = (0..100 * n * n).any(|_| !test.step(program));
+            finished && test.registers[0] == cpu.registers[0] + divisor_sum(n)
+        })
+    };
+    let register = (0..6).find(|&register| sums_divisors(register))?;
+    Some(cpu.registers[0] + divisor_sum(cpu.registers[register]))
+}
+
+/// The program, and whether to skip its loop if it reaches one that
+/// [`skip_loop`] can.
+pub struct Run {
+    program: Program<6>,
+    skip_loop: bool,
+}
+
+impl Run {
+    fn new(program: &Program<6>, part: Part) -> (Cpu<6>, Self) {
+        let (registers, skip_loop) = match part {
+            Part::One => ([0; 6], false),
+            Part::Two => ([1, 0, 0, 0, 0, 0], true),
+        };
+        let run = Run {
+            program: program.clone(),
+            skip_loop,
+        };
+        (Cpu::new(registers), run)
+    }
+
+    /// Does the next instruction, returning `false` once the program has
+    /// finished or reached a loop it can skip.
+    fn advance(&self, cpu: &mut Cpu<6>) -> bool {
+        let from = cpu.ip;
+        if !cpu.step(&self.program) {
+            return false;
+        }
+        !(self.skip_loop && reached_loop(from, cpu) && skip_loop(&self.program, cpu).is_some())
+    }
+
+    fn register0(&self, cpu: &Cpu<6>) -> usize {
+        match self.skip_loop {
+            true => skip_loop(&self.program, cpu).unwrap_or(cpu.registers[0]),
+            false => cpu.registers[0],
+        }
+    }
+
+    fn finish(&self, mut cpu: Cpu<6>) -> usize {
+        while self.advance(&mut cpu) {}
+        self.register0(&cpu)
+    }
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u32 = 19;
+    const INPUT: &'static str = include_str!("input.txt");
+    const ANSWERS: &'static str = include_str!("answers.txt");
+
+    type Input = Program<6>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> parse::Result<Program<6>> {
+        Program::parse(input)
+    }
+
+    fn part1(program: &Program<6>) -> usize {
+        let (cpu, run) = Run::new(program, Part::One);
+        run.finish(cpu)
+    }
+
+    fn part2(program: &Program<6>) -> usize {
+        let (cpu, run) = Run::new(program, Part::Two);
+        run.finish(cpu)
+    }
+}
+
+/// Part 2 is saved while the program sets up, and stops there to skip the
+/// loop if it can.
+impl Resume for Day19 {
+    type State = Cpu<6>;
+    type Context = Run;
+
+    fn start(program: &Program<6>, part: Part) -> Option<(Cpu<6>, Run)> {
+        Some(Run::new(program, part))
+    }
+
+    fn advance(cpu: &mut Cpu<6>, run: &Run) -> bool {
+        run.advance(cpu)
+    }
+
+    fn finish(cpu: &Cpu<6>, run: &Run) -> Answer {
+        run.register0(cpu).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+#ip 0
+seti 5 0 1
+seti 6 0 2
+addi 0 1 0
+addr 1 2 3
+setr 1 0 0
+seti 8 0 4
+seti 9 0 5";
+
+    #[test]
+    fn run_example() {
+        let mut cpu = Cpu::new([0; 6]);
+        cpu.run(&Day19::parse(EXAMPLE).unwrap());
+        assert_eq!((cpu.registers, cpu.ip), ([6, 5, 6, 0, 0, 9], 7));
+        // it has no loop to skip
+        assert_eq!(Day19::part2(&Day19::parse(EXAMPLE).unwrap()), 6);
+    }
+
+    /// Runs `program` from `registers` to its loop.
+    fn to_loop(program: &Program<6>, registers: [usize; 6]) -> Cpu<6> {
+        let mut cpu = Cpu::new(registers);
+        loop {
+            let from = cpu.ip;
+            assert!(cpu.step(program), "the program has no loop");
+            if reached_loop(from, &cpu) {
+                return cpu;
+            }
+        }
+    }
+
+    #[test]
+    fn skipping_matches_running_the_program() {
+        let program = Day19::parse(Day19::INPUT).unwrap();
+        let cpu = to_loop(&program, [0; 6]);
+        assert_eq!(skip_loop(&program, &cpu), Some(Day19::part1(&program)));
+    }
+
+    #[test]
+    fn runs_a_loop_it_cant_skip() {
+        // adds 1 to register 0 for every divisor, so counts them instead
+        let input = Day19::INPUT.replacen("addr 4 0 0", "addi 0 1 0", 1);
+        assert_ne!(input, Day19::INPUT);
+        let program = Day19::parse(&input).unwrap();
+        let cpu = to_loop(&program, [0; 6]);
+        assert_eq!(skip_loop(&program, &cpu), None);
+
+        let run = Run {
+            program: program.clone(),
+            skip_loop: true,
+        };
+        assert_eq!(run.finish(Cpu::new([0; 6])), Day19::part1(&program));
+    }
+}
@@ -0,0 +1,271 @@
+use crate::{
+    answer::Answer,
+    day03::{self, Day03},
+    day05::{self, Day05},
+    day11::{self, Day11},
+    day12::{self, Day12},
+    generate,
+    parse::{self, ParseError},
+    registry,
+    worker::panic_message,
+    Solution,
+};
+use std::{
+    fmt,
+    ops::Range,
+    panic::{self, AssertUnwindSafe},
+};
+
+/// What an implementation gave: its answer, or the message it panicked with.
+pub type Outcome = Result<Answer, String>;
+
+fn outcome(answer: impl FnOnce() -> Answer) -> Outcome {
+    panic::catch_unwind(AssertUnwindSafe(answer)).map_err(|payload| panic_message(&*payload))
+}
+
+/// A faster implementation and the straightforward one it replaced, which
+/// should always agree.
+pub struct Check {
+    pub day: u32,
+    /// What's being compared.
+    pub name: &'static str,
+    /// Parses an input and gives the reference outcome, then the optimized
+    /// one.
+    compare: fn(&str) -> parse::Result<(Outcome, Outcome)>,
+}
+
+pub static CHECKS: [Check; 6] = [
+    Check {
+        day: 3,
+        name: "overlap",
+        compare: |input| {
+            let claims = Day03::parse(input)?;
+            Ok((
+                outcome(|| day03::reference::part1(&claims).into()),
+                outcome(|| Day03::part1(&claims).into()),
+            ))
+        },
+    },
+    Check {
+        day: 3,
+        name: "intact claim",
+        compare: |input| {
+            let claims = Day03::parse(input)?;
+            Ok((
+                outcome(|| day03::reference::part2(&claims).into()),
+                outcome(|| Day03::part2(&claims).into()),
+            ))
+        },
+    },
+    Check {
+        day: 5,
+        name: "react",
+        compare: |input| {
+            let polymer = Day05::parse(input)?;
+            Ok((
+                outcome(|| day05::reference::react(&polymer).into()),
+                outcome(|| day05::react(polymer.chars()).into()),
+            ))
+        },
+    },
+    Check {
+        day: 5,
+        name: "shortest polymer",
+        compare: |input| {
+            let polymer = Day05::parse(input)?;
+            Ok((
+                outcome(|| day05::reference::part2(&polymer).into()),
+                outcome(|| Day05::part2(&polymer).into()),
+            ))
+        },
+    },
+    Check {
+        day: 11,
+        name: "largest square",
+        compare: |input| {
+            let serial_number = Day11::parse(input)?;
+            Ok((
+                outcome(|| day11::reference::part2(serial_number).into()),
+                outcome(|| Day11::part2(&serial_number).into()),
+            ))
+        },
+    },
+    Check {
+        day: 12,
+        name: "plant sums",
+        // generated rules rarely settle down, so this stops well short of
+        // part 2's fifty billion generations
+        compare: |input| {
+            let pots = Day12::parse(input)?;
+            let sums = |sum_after: fn(&day12::Pots, usize) -> i64| {
+                let sums = [sum_after(&pots, 20), sum_after(&pots, 300)];
+                Answer::from((sums[0], sums[1]))
+            };
+            Ok((
+                outcome(|| sums(day12::reference::sum_after)),
+                outcome(|| sums(day12::sum_after)),
+            ))
+        },
+    },
+];
+
+/// The checks for `day`.
+pub fn find(day: u32) -> impl Iterator<Item = &'static Check> {
+    CHECKS.iter().filter(move |check| check.day == day)
+}
+
+/// Where an input came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Source {
+    Bundled,
+    Generated { seed: u64, size: usize },
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Source::Bundled => write!(f, "the bundled input"),
+            Source::Generated { seed, size } => {
+                write!(
+                    f,
+                    "the input generated with seed {} and size {}",
+                    seed, size
+                )
+            }
+        }
+    }
+}
+
+/// The bundled input for `day`, then one generated input for each of
+/// `seeds`, each of `size` or the generator's usual size.
+pub fn inputs(
+    day: u32,
+    seeds: Range<u64>,
+    size: Option<usize>,
+) -> impl Iterator<Item = (Source, String)> {
+    let bundled = registry::find(day).map(|puzzle| (Source::Bundled, puzzle.input(None).unwrap()));
+    let generated = generate::find(day).into_iter().flat_map(move |generator| {
+        let size = size.unwrap_or(generator.default_size);
+        seeds.clone().map(move |seed| {
+            let input = generator.generate(seed, size);
+            (Source::Generated { seed, size }, input)
+        })
+    });
+    bundled.into_iter().chain(generated)
+}
+
+/// Why a check stopped.
+#[derive(Debug)]
+pub enum Failure {
+    /// The input didn't parse, which means the generator is broken.
+    Invalid {
+        source: Source,
+        error: ParseError,
+    },
+    Diverged(Divergence),
+}
+
+/// The first input a check's implementations disagreed on.
+#[derive(Debug)]
+pub struct Divergence {
+    pub source: Source,
+    pub input: String,
+    pub reference: Outcome,
+    pub optimized: Outcome,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Failure::Invalid { source, error } => write!(f, "{} is invalid: {}", source, error),
+            Failure::Diverged(divergence) => {
+                let show = |outcome: &Outcome| match outcome {
+                    Ok(answer) => answer.to_string(),
+                    Err(message) => format!("panicked: {}", message),
+                };
+                writeln!(f, "the implementations disagree on {}", divergence.source)?;
+                writeln!(f, "  reference: {}", show(&divergence.reference))?;
+                writeln!(f, "  optimized: {}", show(&divergence.optimized))?;
+                write!(f, "input:\n{}", divergence.input.trim_end())
+            }
+        }
+    }
+}
+
+impl Check {
+    /// Compares the implementations on each of `inputs` in turn, stopping at
+    /// the first that they disagree on. Returns how many inputs they agreed
+    /// on.
+    pub fn run(
+        &self,
+        inputs: impl IntoIterator<Item = (Source, String)>,
+    ) -> Result<usize, Failure> {
+        let mut agreed = 0;
+        for (source, input) in inputs {
+            let (reference, optimized) =
+                (self.compare)(&input).map_err(|error| Failure::Invalid { source, error })?;
+            if reference != optimized {
+                return Err(Failure::Diverged(Divergence {
+                    source,
+                    input,
+                    reference,
+                    optimized,
+                }));
+            }
+            agreed += 1;
+        }
+        Ok(agreed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn implementations_agree() {
+        for check in &CHECKS {
+            // day 11's reference takes a while, however small the input
+            let (seeds, sizes): (_, &[_]) = if check.day == 11 {
+                (0..1, &[None])
+            } else {
+                (0..3, &[Some(20), None])
+            };
+            for &size in sizes {
+                if let Err(failure) = check.run(inputs(check.day, seeds.clone(), size)) {
+                    panic!("day {} {}: {}", check.day, check.name, failure);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn reports_divergence() {
+        let check = Check {
+            day: 5,
+            name: "length",
+            compare: |input| {
+                let polymer = Day05::parse(input)?;
+                Ok((
+                    outcome(|| polymer.len().into()),
+                    outcome(|| {
+                        assert!(polymer.len() < 5, "too long");
+                        polymer.len().into()
+                    }),
+                ))
+            },
+        };
+        let inputs = vec![
+            (Source::Bundled, "abc".to_string()),
+            (Source::Generated { seed: 1, size: 9 }, "aBcDeF".to_string()),
+        ];
+        match check.run(inputs) {
+            Err(Failure::Diverged(divergence)) => {
+                assert_eq!(divergence.source, Source::Generated { seed: 1, size: 9 });
+                assert_eq!(divergence.reference, Ok(6.into()));
+                assert_eq!(divergence.optimized, Err("too long".to_string()));
+            }
+            other => panic!("expected a divergence, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,327 @@
+use crate::parse::{self, Line};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fmt, str::FromStr};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Opcode {
+    Addr,
+    Addi,
+    Mulr,
+    Muli,
+    Banr,
+    Bani,
+    Borr,
+    Bori,
+    Setr,
+    Seti,
+    Gtir,
+    Gtri,
+    Gtrr,
+    Eqir,
+    Eqri,
+    Eqrr,
+}
+
+impl Opcode {
+    pub const ALL: [Opcode; 16] = [
+        Opcode::Addr,
+        Opcode::Addi,
+        Opcode::Mulr,
+        Opcode::Muli,
+        Opcode::Banr,
+        Opcode::Bani,
+        Opcode::Borr,
+        Opcode::Bori,
+        Opcode::Setr,
+        Opcode::Seti,
+        Opcode::Gtir,
+        Opcode::Gtri,
+        Opcode::Gtrr,
+        Opcode::Eqir,
+        Opcode::Eqri,
+        Opcode::Eqrr,
+    ];
+
+    pub fn mnemonic(self) -> &'static str {
+        use self::Opcode::*;
+        match self {
+            Addr => "addr",
+            Addi => "addi",
+            Mulr => "mulr",
+            Muli => "muli",
+            Banr => "banr",
+            Bani => "bani",
+            Borr => "borr",
+            Bori => "bori",
+            Setr => "setr",
+            Seti => "seti",
+            Gtir => "gtir",
+            Gtri => "gtri",
+            Gtrr => "gtrr",
+            Eqir => "eqir",
+            Eqri => "eqri",
+            Eqrr => "eqrr",
+        }
+    }
+
+    /// Whether operand A names a register rather than being a value.
+    fn a_is_register(self) -> bool {
+        use self::Opcode::*;
+        !matches!(self, Seti | Gtir | Eqir)
+    }
+
+    /// Whether operand B names a register; `set*` ignore it entirely.
+    fn b_is_register(self) -> bool {
+        use self::Opcode::*;
+        matches!(self, Addr | Mulr | Banr | Borr | Gtir | Gtrr | Eqir | Eqrr)
+    }
+}
+
+impl FromStr for Opcode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Opcode::ALL
+            .iter()
+            .cloned()
+            .find(|opcode| opcode.mnemonic() == s)
+            .ok_or(())
+    }
+}
+
+impl fmt::Display for Opcode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.mnemonic().fmt(f)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Inst {
+    pub opcode: Opcode,
+    pub a: usize,
+    pub b: usize,
+    pub c: usize,
+}
+
+impl Inst {
+    pub fn new(opcode: Opcode, a: usize, b: usize, c: usize) -> Self {
+        Inst { opcode, a, b, c }
+    }
+
+    /// Panics if the instruction names a register that doesn't exist.
+    pub fn exec<const N: usize>(&self, registers: &mut [usize; N]) {
+        use self::Opcode::*;
+        let (a, b) = (self.a, self.b);
+        let r = *registers;
+        registers[self.c] = match self.opcode {
+            Addr => r[a] + r[b],
+            Addi => r[a] + b,
+            Mulr => r[a] * r[b],
+            Muli => r[a] * b,
+            Banr => r[a] & r[b],
+            Bani => r[a] & b,
+            Borr => r[a] | r[b],
+            Bori => r[a] | b,
+            Setr => r[a],
+            Seti => a,
+            Gtir => (a > r[b]) as usize,
+            Gtri => (r[a] > b) as usize,
+            Gtrr => (r[a] > r[b]) as usize,
+            Eqir => (a == r[b]) as usize,
+            Eqri => (r[a] == b) as usize,
+            Eqrr => (r[a] == r[b]) as usize,
+        }
+    }
+}
+
+impl fmt::Display for Inst {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {} {}", self.opcode, self.a, self.b, self.c)
+    }
+}
+
+/// An instruction whose opcode is still a number, as in the device manual.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RawInst {
+    pub opcode: usize,
+    pub a: usize,
+    pub b: usize,
+    pub c: usize,
+}
+
+impl RawInst {
+    /// Parses a line like `9 2 1 2`.
+    pub fn parse(mut line: Line) -> parse::Result<Self> {
+        let inst = RawInst {
+            opcode: line.number()?,
+            a: line.number()?,
+            b: line.number()?,
+            c: line.number()?,
+        };
+        line.end()?;
+        Ok(inst)
+    }
+
+    pub fn decode(&self, opcodes: &HashMap<usize, Opcode>) -> Option<Inst> {
+        Some(Inst::new(
+            *opcodes.get(&self.opcode)?,
+            self.a,
+            self.b,
+            self.c,
+        ))
+    }
+}
+
+/// Elfcode for a machine with `N` registers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Program<const N: usize> {
+    /// The register the instruction pointer is bound to, if any.
+    pub ip: Option<usize>,
+    pub insts: Vec<Inst>,
+}
+
+impl<const N: usize> Program<N> {
+    /// Parses an optional `#ip` line followed by one instruction per line,
+    /// like `seti 5 0 1`.
+    pub fn parse(input: &str) -> parse::Result<Self> {
+        let mut lines = parse::lines(input).peekable();
+        let mut ip = None;
+        if let Some(mut line) = lines.next_if(|line| line.rest().starts_with("#ip")) {
+            line.literal("#ip")?;
+            ip = Some(register::<N>(&mut line)?);
+            line.end()?;
+        }
+        let insts = lines
+            .map(|mut line| {
+                line.skip_spaces();
+                let at = line;
+                let opcode: Opcode = line
+                    .word()
+                    .parse()
+                    .map_err(|_| at.error("an opcode like `addr`"))?;
+                let a = operand::<N>(&mut line, opcode.a_is_register())?;
+                let b = operand::<N>(&mut line, opcode.b_is_register())?;
+                let c = register::<N>(&mut line)?;
+                line.end()?;
+                Ok(Inst::new(opcode, a, b, c))
+            })
+            .collect::<parse::Result<_>>()?;
+        Ok(Program { ip, insts })
+    }
+
+    /// Decodes instructions with numeric opcodes, as long as every opcode is
+    /// in `opcodes`.
+    pub fn load(raw: &[RawInst], opcodes: &HashMap<usize, Opcode>) -> Option<Self> {
+        Some(Program {
+            ip: None,
+            insts: raw
+                .iter()
+                .map(|inst| inst.decode(opcodes))
+                .collect::<Option<_>>()?,
+        })
+    }
+}
+
+fn register<const N: usize>(line: &mut Line) -> parse::Result<usize> {
+    operand::<N>(line, true)
+}
+
+fn operand<const N: usize>(line: &mut Line, is_register: bool) -> parse::Result<usize> {
+    line.skip_spaces();
+    let at = *line;
+    let n = line.number()?;
+    if is_register && n >= N {
+        return Err(at.error(&format!("a register from 0 to {}", N - 1)));
+    }
+    Ok(n)
+}
+
+/// The state of a machine with `N` registers running a program.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "[usize; N]: Serialize",
+    deserialize = "[usize; N]: Deserialize<'de>"
+))]
+pub struct Cpu<const N: usize> {
+    pub registers: [usize; N],
+    pub ip: usize,
+}
+
+impl<const N: usize> Cpu<N> {
+    pub fn new(registers: [usize; N]) -> Self {
+        Cpu { registers, ip: 0 }
+    }
+
+    /// Executes one instruction, or returns `false` without doing anything if
+    /// the instruction pointer is outside the program.
+    pub fn step(&mut self, program: &Program<N>) -> bool {
+        let inst = match program.insts.get(self.ip) {
+            Some(inst) => inst,
+            None => return false,
+        };
+        if let Some(ip) = program.ip {
+            self.registers[ip] = self.ip;
+        }
+        inst.exec(&mut self.registers);
+        if let Some(ip) = program.ip {
+            self.ip = self.registers[ip];
+        }
+        self.ip += 1;
+        true
+    }
+
+    /// Runs until the instruction pointer leaves the program.
+    pub fn run(&mut self, program: &Program<N>) {
+        while self.step(program) {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mnemonics_round_trip() {
+        for &opcode in &Opcode::ALL {
+            assert_eq!(opcode.mnemonic().parse(), Ok(opcode));
+        }
+        assert_eq!("nope".parse::<Opcode>(), Err(()));
+    }
+
+    #[test]
+    fn rejects_missing_registers() {
+        let error = Program::<4>::parse("seti 9 0 1\naddr 1 4 2").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 8: expected a register from 0 to 3, found `4`"
+        );
+        assert!(Program::<4>::parse("seti 9 0 1\naddi 1 4 2").is_ok());
+    }
+
+    #[test]
+    fn loads_numeric_opcodes() {
+        let opcodes = [(0, Opcode::Seti), (1, Opcode::Mulr)]
+            .iter()
+            .cloned()
+            .collect();
+        let raw = [
+            RawInst {
+                opcode: 0,
+                a: 6,
+                b: 0,
+                c: 1,
+            },
+            RawInst {
+                opcode: 1,
+                a: 1,
+                b: 1,
+                c: 0,
+            },
+        ];
+        let program = Program::<4>::load(&raw, &opcodes).unwrap();
+        let mut cpu = Cpu::new([0; 4]);
+        cpu.run(&program);
+        assert_eq!(cpu.registers, [36, 6, 0, 0]);
+        assert_eq!(Program::<4>::load(&raw[..1], &HashMap::new()), None);
+    }
+}
@@ -0,0 +1,549 @@
+use std::{collections::HashSet, fmt::Write};
+
+/// A small, fast random number generator (SplitMix64), so that a seed gives
+/// the same input on every platform and forever after.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number from `0` to `n - 1`.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "can't pick a number below 0");
+        ((u128::from(self.next_u64()) * n as u128) >> 64) as usize
+    }
+
+    /// A number from `low` to `high`, inclusive.
+    pub fn between(&mut self, low: i64, high: i64) -> i64 {
+        low + self.below((high - low + 1) as usize) as i64
+    }
+
+    /// True one time in `n`, on average.
+    pub fn one_in(&mut self, n: usize) -> bool {
+        self.below(n) == 0
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// Writes random puzzle inputs for one day's format.
+///
+/// The inputs are always well formed, but not always solvable: carts on
+/// separate loops never crash.
+pub struct Generator {
+    pub day: u32,
+    /// What the size counts.
+    pub size_unit: &'static str,
+    /// A size like the real inputs'.
+    pub default_size: usize,
+    generate: fn(&mut Rng, usize) -> String,
+}
+
+impl Generator {
+    pub fn generate(&self, seed: u64, size: usize) -> String {
+        (self.generate)(&mut Rng::new(seed), size)
+    }
+}
+
+pub static GENERATORS: [Generator; 12] = [
+    Generator {
+        day: 3,
+        size_unit: "claims",
+        default_size: 1300,
+        generate: claims,
+    },
+    Generator {
+        day: 5,
+        size_unit: "units",
+        default_size: 50000,
+        generate: polymer,
+    },
+    Generator {
+        day: 6,
+        size_unit: "coordinates",
+        default_size: 50,
+        generate: coordinates,
+    },
+    Generator {
+        day: 7,
+        size_unit: "steps, up to 26",
+        default_size: 26,
+        generate: dependencies,
+    },
+    Generator {
+        day: 8,
+        size_unit: "nodes",
+        default_size: 2000,
+        generate: license_tree,
+    },
+    Generator {
+        day: 10,
+        size_unit: "stars",
+        default_size: 350,
+        generate: star_field,
+    },
+    Generator {
+        day: 11,
+        size_unit: "the largest serial number",
+        default_size: 9999,
+        generate: serial_number,
+    },
+    Generator {
+        day: 12,
+        size_unit: "pots",
+        default_size: 100,
+        generate: pots,
+    },
+    Generator {
+        day: 13,
+        size_unit: "cells along each side",
+        default_size: 150,
+        generate: track_map,
+    },
+    Generator {
+        day: 15,
+        size_unit: "cells along each side",
+        default_size: 32,
+        generate: battle_map,
+    },
+    Generator {
+        day: 17,
+        size_unit: "basins",
+        default_size: 200,
+        generate: clay_scan,
+    },
+    Generator {
+        day: 18,
+        size_unit: "acres along each side",
+        default_size: 50,
+        generate: forest,
+    },
+];
+
+pub fn find(day: u32) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|generator| generator.day == day)
+}
+
+/// Claims on a 1000 inch square of fabric, with the odd empty one.
+fn claims(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    for id in 1..=size {
+        let (width, height) = if rng.one_in(50) {
+            (0, rng.between(0, 29))
+        } else {
+            (rng.between(1, 29), rng.between(1, 29))
+        };
+        let x = rng.between(0, 1000 - width);
+        let y = rng.between(0, 1000 - height);
+        writeln!(input, "#{} @ {},{}: {}x{}", id, x, y, width, height).unwrap();
+    }
+    input
+}
+
+/// A polymer of a few unit types, where most units are next to one that
+/// reacts with them or will once the units between have reacted.
+fn polymer(rng: &mut Rng, size: usize) -> String {
+    let types = b"abcdefghij";
+    let mut units = Vec::new();
+    // units waiting for their opposite, innermost last
+    let mut open = Vec::new();
+    while units.len() < size.max(1) {
+        match open.pop() {
+            Some(unit) if rng.one_in(2) => units.push(opposite(unit)),
+            Some(unit) => open.push(unit),
+            None => {}
+        }
+        if units.len() < size && !rng.one_in(3) {
+            let mut unit = types[rng.below(types.len())];
+            if rng.one_in(2) {
+                unit = unit.to_ascii_uppercase();
+            }
+            units.push(unit);
+            open.push(unit);
+        }
+    }
+    String::from_utf8(units).unwrap() + "\n"
+}
+
+fn opposite(unit: u8) -> u8 {
+    if unit.is_ascii_uppercase() {
+        unit.to_ascii_lowercase()
+    } else {
+        unit.to_ascii_uppercase()
+    }
+}
+
+/// Distinct coordinates well inside the area day 6 searches.
+fn coordinates(rng: &mut Rng, size: usize) -> String {
+    let size = size.min(300 * 300);
+    let mut seen = HashSet::new();
+    let mut input = String::new();
+    while seen.len() < size {
+        let pos = (rng.between(50, 349), rng.between(50, 349));
+        if seen.insert(pos) {
+            writeln!(input, "{}, {}", pos.0, pos.1).unwrap();
+        }
+    }
+    input
+}
+
+/// Constraints between lettered steps that never form a cycle.
+fn dependencies(rng: &mut Rng, size: usize) -> String {
+    let mut steps: Vec<char> = ('A'..='Z').collect();
+    rng.shuffle(&mut steps);
+    steps.truncate(size.clamp(2, 26));
+    let mut constraints = Vec::new();
+    for (i, &step) in steps.iter().enumerate().skip(1) {
+        // something before every step, so that none of them go missing
+        constraints.push((steps[rng.below(i)], step));
+        for &prereq in &steps[..i] {
+            if rng.one_in(4) && !constraints.contains(&(prereq, step)) {
+                constraints.push((prereq, step));
+            }
+        }
+    }
+    rng.shuffle(&mut constraints);
+    let mut input = String::new();
+    for (prereq, step) in constraints {
+        writeln!(
+            input,
+            "Step {} must be finished before step {} can begin.",
+            prereq, step
+        )
+        .unwrap();
+    }
+    input
+}
+
+/// A tree whose nodes each have up to 255 children and 1 to 11 metadata
+/// entries, some pointing at children that don't exist.
+fn license_tree(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut children = vec![Vec::new(); size];
+    for node in 1..size {
+        let parent = rng.below(node);
+        // the node just before has no children yet
+        let parent = if children[parent].len() < 255 {
+            parent
+        } else {
+            node - 1
+        };
+        children[parent].push(node);
+    }
+    let mut numbers = Vec::new();
+    // nodes to write, or `None` for "write the metadata of the node before"
+    let mut stack = vec![Some(0)];
+    let mut metadata_counts = Vec::new();
+    while let Some(entry) = stack.pop() {
+        match entry {
+            Some(node) => {
+                let metadata = rng.between(1, 11) as usize;
+                numbers.push(children[node].len());
+                numbers.push(metadata);
+                metadata_counts.push((metadata, children[node].len()));
+                stack.push(None);
+                stack.extend(children[node].iter().rev().map(|&child| Some(child)));
+            }
+            None => {
+                let (metadata, child_count) = metadata_counts.pop().unwrap();
+                for _ in 0..metadata {
+                    numbers.push(rng.between(1, (child_count as i64 + 2).min(255)) as usize);
+                }
+            }
+        }
+    }
+    let numbers: Vec<_> = numbers.iter().map(usize::to_string).collect();
+    numbers.join(" ") + "\n"
+}
+
+/// Stars that line up into a block of random dots some seconds from now.
+fn star_field(rng: &mut Rng, size: usize) -> String {
+    let seconds = rng.between(1000, 15000);
+    let width = (size as i64 / 4).max(10);
+    let mut input = String::new();
+    for _ in 0..size {
+        let (x, y) = (rng.between(0, width), rng.between(0, 9));
+        let (dx, dy) = loop {
+            let velocity = (rng.between(-5, 5), rng.between(-5, 5));
+            if velocity != (0, 0) {
+                break velocity;
+            }
+        };
+        writeln!(
+            input,
+            "position=<{:>6}, {:>6}> velocity=<{:>2}, {:>2}>",
+            x - dx * seconds,
+            y - dy * seconds,
+            dx,
+            dy
+        )
+        .unwrap();
+    }
+    input
+}
+
+fn serial_number(rng: &mut Rng, size: usize) -> String {
+    format!("{}\n", rng.between(1, size.max(1) as i64))
+}
+
+/// A row of pots and a rule for every pattern. An empty pattern stays empty
+/// so that plants can't appear from nowhere, and a plant always grows two
+/// pots after the last one so that they can't all die out.
+fn pots(rng: &mut Rng, size: usize) -> String {
+    let pot = |has_plant| if has_plant { '#' } else { '.' };
+    let mut input = String::from("initial state: ");
+    input.extend((0..size.max(1)).map(|i| pot(i == 0 || rng.one_in(2))));
+    input.push_str("\n\n");
+    let mut patterns: Vec<usize> = (0..32).collect();
+    rng.shuffle(&mut patterns);
+    for pattern in patterns {
+        let grows = match pattern {
+            0b00000 => false,
+            0b10000 => true,
+            _ => rng.one_in(2),
+        };
+        let pattern: String = (0..5).rev().map(|i| pot(pattern >> i & 1 == 1)).collect();
+        writeln!(input, "{} => {}", pattern, pot(grows)).unwrap();
+    }
+    input
+}
+
+/// Rectangular loops of track crossing each other, with an odd number of
+/// carts on their straight parts.
+fn track_map(rng: &mut Rng, size: usize) -> String {
+    let side = size.max(8);
+    let mut map = vec![vec![' '; side]; side];
+    for _ in 0..side * 2 {
+        let (left, right) = ordered(rng, side);
+        let (top, bottom) = ordered(rng, side);
+        if right - left < 2 || bottom - top < 2 {
+            continue;
+        }
+        let mut cells = Vec::new();
+        for x in left..=right {
+            for &y in &[top, bottom] {
+                let corner = if x == left || x == right {
+                    Some(if (x == left) == (y == top) { '/' } else { '\\' })
+                } else {
+                    None
+                };
+                cells.push((x, y, corner.unwrap_or('-')));
+            }
+        }
+        for y in top + 1..bottom {
+            for &x in &[left, right] {
+                cells.push((x, y, '|'));
+            }
+        }
+        // only cross other track at right angles, away from corners
+        let fits = cells
+            .iter()
+            .all(|&(x, y, c)| matches!((map[y][x], c), (' ', _) | ('|', '-') | ('-', '|')));
+        if fits {
+            for (x, y, c) in cells {
+                map[y][x] = if map[y][x] == ' ' { c } else { '+' };
+            }
+        }
+    }
+    let mut straights: Vec<_> = (0..side)
+        .flat_map(|y| (0..side).map(move |x| (x, y)))
+        .filter(|&(x, y)| map[y][x] == '-' || map[y][x] == '|')
+        .collect();
+    rng.shuffle(&mut straights);
+    let carts = (side / 10).max(1) | 1;
+    for &(x, y) in straights.iter().take(carts) {
+        map[y][x] = match (map[y][x], rng.one_in(2)) {
+            ('-', true) => '<',
+            ('-', false) => '>',
+            (_, true) => '^',
+            (_, false) => 'v',
+        };
+    }
+    lines(&map)
+}
+
+/// Two different numbers below `side`, smaller first.
+fn ordered(rng: &mut Rng, side: usize) -> (usize, usize) {
+    let (a, b) = (rng.below(side), rng.below(side));
+    (a.min(b), a.max(b))
+}
+
+/// A walled cave with scattered rock. Some pockets may be cut off, but the
+/// units all start in the biggest open space, so every fight can finish.
+fn battle_map(rng: &mut Rng, size: usize) -> String {
+    let side = size.max(5);
+    let mut map = vec![vec!['#'; side]; side];
+    for (y, row) in map.iter_mut().enumerate().take(side - 1).skip(1) {
+        for (x, cell) in row.iter_mut().enumerate().take(side - 1).skip(1) {
+            if (x, y) == (1, 1) || !rng.one_in(4) {
+                *cell = '.';
+            }
+        }
+    }
+    let mut floor = largest_space(&map);
+    rng.shuffle(&mut floor);
+    let units = (floor.len() / 30).max(2).min(floor.len());
+    for (i, &(x, y)) in floor.iter().take(units).enumerate() {
+        map[y][x] = if i % 2 == 0 { 'E' } else { 'G' };
+    }
+    lines(&map)
+}
+
+/// The floor cells of the biggest space in `map` that's open all the way
+/// across.
+fn largest_space(map: &[Vec<char>]) -> Vec<(usize, usize)> {
+    let mut seen = HashSet::new();
+    let mut largest = Vec::new();
+    for (y, row) in map.iter().enumerate() {
+        for (x, &cell) in row.iter().enumerate() {
+            if cell != '.' || !seen.insert((x, y)) {
+                continue;
+            }
+            let mut space = vec![(x, y)];
+            let mut unvisited = vec![(x, y)];
+            while let Some((x, y)) = unvisited.pop() {
+                // the cave's walls keep every neighbour in bounds
+                for next in [(x, y - 1), (x - 1, y), (x + 1, y), (x, y + 1)] {
+                    if map[next.1][next.0] == '.' && seen.insert(next) {
+                        space.push(next);
+                        unvisited.push(next);
+                    }
+                }
+            }
+            if space.len() > largest.len() {
+                largest = space;
+            }
+        }
+    }
+    largest.sort_unstable();
+    largest
+}
+
+/// Basins, walls and shelves of clay around the spring at `x=500`.
+fn clay_scan(rng: &mut Rng, size: usize) -> String {
+    let spread = (size as i64).clamp(10, 450);
+    let depth = (size as i64 * 8).max(20);
+    let mut input = String::new();
+    for _ in 0..size.max(1) {
+        let left = 500 + rng.between(-spread, spread);
+        let top = rng.between(1, depth);
+        let (width, height) = (rng.between(2, 16), rng.between(1, 12));
+        match rng.below(6) {
+            0 => writeln!(input, "x={}, y={}..{}", left, top, top + height),
+            1 => writeln!(input, "y={}, x={}..{}", top, left, left + width),
+            _ => {
+                let (right, bottom) = (left + width, top + height);
+                writeln!(input, "x={}, y={}..{}", left, top, bottom).unwrap();
+                writeln!(input, "x={}, y={}..{}", right, top, bottom).unwrap();
+                writeln!(input, "y={}, x={}..{}", bottom, left, right)
+            }
+        }
+        .unwrap();
+    }
+    input
+}
+
+fn forest(rng: &mut Rng, size: usize) -> String {
+    let side = size.max(1);
+    let map: Vec<Vec<_>> = (0..side)
+        .map(|_| {
+            (0..side)
+                .map(|_| match rng.below(20) {
+                    0..=8 => '.',
+                    9..=15 => '|',
+                    _ => '#',
+                })
+                .collect()
+        })
+        .collect();
+    lines(&map)
+}
+
+fn lines(map: &[Vec<char>]) -> String {
+    map.iter()
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn inputs_parse() {
+        for generator in &GENERATORS {
+            let puzzle = registry::find(generator.day).unwrap();
+            for seed in 0..5 {
+                for &size in &[1, 10, generator.default_size] {
+                    let input = generator.generate(seed, size);
+                    if let Err(error) = puzzle.parse(&input) {
+                        panic!(
+                            "day {} seed {} size {}: {}\n{}",
+                            generator.day, seed, size, error, input
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn seeds_repeat() {
+        for generator in &GENERATORS {
+            let size = generator.default_size;
+            assert_eq!(generator.generate(7, size), generator.generate(7, size));
+            assert_ne!(generator.generate(7, size), generator.generate(8, size));
+        }
+    }
+
+    #[test]
+    fn stars_line_up() {
+        let day10 = registry::find(10).unwrap();
+        for seed in 0..3 {
+            let input = find(10).unwrap().generate(seed, 100);
+            let message = day10.solve(&*day10.parse(&input).unwrap(), registry::Part::One);
+            assert_eq!(message.to_string().lines().count(), 10);
+        }
+    }
+
+    #[test]
+    fn armies_can_meet() {
+        for seed in 0..20 {
+            for &size in &[5, 10, 32] {
+                let input = find(15).unwrap().generate(seed, size);
+                let map: Vec<Vec<char>> = input
+                    .lines()
+                    .map(|line| {
+                        line.chars()
+                            .map(|c| if c == '#' { c } else { '.' })
+                            .collect()
+                    })
+                    .collect();
+                let space: HashSet<_> = largest_space(&map).into_iter().collect();
+                for (y, line) in input.lines().enumerate() {
+                    for (x, c) in line.char_indices() {
+                        if c == 'E' || c == 'G' {
+                            assert!(space.contains(&(x, y)), "seed {} size {}", seed, size);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,312 @@
+use crate::parse;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+/// A position in a grid: `(x, y)`, with `y` growing downwards.
+pub type Pos = (usize, usize);
+
+/// The key that sorts positions top to bottom, then left to right.
+pub fn reading_order((x, y): Pos) -> (usize, usize) {
+    (y, x)
+}
+
+/// A rectangular map of cells, stored row by row.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut cell: impl FnMut(Pos) -> T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: (0..height)
+                .flat_map(|y| (0..width).map(move |x| (x, y)))
+                .map(&mut cell)
+                .collect(),
+        }
+    }
+
+    /// Parses a character map, one row per line, with `cell` returning `None`
+    /// for a character that isn't one of `expected`. Every row must be as wide
+    /// as the first.
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> parse::Result<Self> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for mut line in parse::lines(input) {
+            let start = cells.len();
+            while let Some(c) = line.peek() {
+                if width == Some(cells.len() - start) {
+                    return Err(line.error("end of line"));
+                }
+                cells.push(cell(c).ok_or_else(|| line.error(expected))?);
+                line.next_char(expected)?;
+            }
+            match width {
+                Some(width) if cells.len() - start < width => return Err(line.error(expected)),
+                Some(_) => {}
+                None => width = Some(cells.len()),
+            }
+            height += 1;
+        }
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    /// Like [`Grid::parse`], but rows may be any width: short ones are
+    /// padded out to the widest with `fill`, as if trailing spaces had been
+    /// trimmed from them.
+    pub fn parse_ragged(
+        input: &str,
+        expected: &str,
+        fill: T,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> parse::Result<Self>
+    where
+        T: Clone,
+    {
+        let mut rows = Vec::new();
+        for mut line in parse::lines(input) {
+            let mut row = Vec::new();
+            while let Some(c) = line.peek() {
+                row.push(cell(c).ok_or_else(|| line.error(expected))?);
+                line.next_char(expected)?;
+            }
+            rows.push(row);
+        }
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let height = rows.len();
+        let cells = rows
+            .into_iter()
+            .flat_map(|mut row| {
+                row.resize(width, fill.clone());
+                row
+            })
+            .collect();
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        if self.contains(pos) {
+            Some(&self[pos])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self[pos])
+        } else {
+            None
+        }
+    }
+
+    /// Every position, in reading order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Every cell with its position, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// Every cell, in reading order.
+    pub fn cells(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a zero width, which only an empty grid has
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The positions above, left of, right of and below `pos` that are inside
+    /// the grid, in reading order.
+    pub fn neighbours4(&self, (x, y): Pos) -> impl Iterator<Item = Pos> + '_ {
+        let candidates = [
+            (Some(x), y.checked_sub(1)),
+            (x.checked_sub(1), Some(y)),
+            (x.checked_add(1), Some(y)),
+            (Some(x), y.checked_add(1)),
+        ];
+        self.inside(candidates)
+    }
+
+    /// The positions around `pos`, including diagonals, that are inside the
+    /// grid, in reading order.
+    pub fn neighbours8(&self, (x, y): Pos) -> impl Iterator<Item = Pos> + '_ {
+        let (up, left, right, down) = (
+            y.checked_sub(1),
+            x.checked_sub(1),
+            x.checked_add(1),
+            y.checked_add(1),
+        );
+        let candidates = [
+            (left, up),
+            (Some(x), up),
+            (right, up),
+            (left, Some(y)),
+            (right, Some(y)),
+            (left, down),
+            (Some(x), down),
+            (right, down),
+        ];
+        self.inside(candidates)
+    }
+
+    fn inside<const N: usize>(
+        &self,
+        candidates: [(Option<usize>, Option<usize>); N],
+    ) -> impl Iterator<Item = Pos> + '_ {
+        IntoIterator::into_iter(candidates)
+            .filter_map(|(x, y)| Some((x?, y?)))
+            .filter(move |&pos| self.contains(pos))
+    }
+
+    pub fn map<U>(&self, mut f: impl FnMut(Pos, &T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.iter().map(|(pos, cell)| f(pos, cell)).collect(),
+        }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): Pos) -> &T {
+        assert!(self.contains((x, y)), "({}, {}) is outside the grid", x, y);
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, (x, y): Pos) -> &mut T {
+        assert!(self.contains((x, y)), "({}, {}) is outside the grid", x, y);
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+/// Writes each row of cells on its own line.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                cell.fmt(f)?;
+            }
+            '\n'.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> parse::Result<Grid<u32>> {
+        Grid::parse(input, "a digit", |c| c.to_digit(10))
+    }
+
+    #[test]
+    fn parses_and_displays() {
+        let grid = digits("123\n456").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.to_string(), "123\n456\n");
+    }
+
+    #[test]
+    fn rejects_ragged_rows() {
+        let short = digits("123\n45\n789").unwrap_err();
+        assert_eq!((short.line, short.column), (2, 3));
+        assert_eq!(short.found, "end of line");
+        let long = digits("123\n4567").unwrap_err();
+        assert_eq!((long.line, long.column), (2, 4));
+        assert_eq!(long.expected, "end of line");
+    }
+
+    #[test]
+    fn pads_ragged_rows() {
+        let grid = Grid::parse_ragged("12\n3456\n\n7", "a digit", 0, |c| c.to_digit(10)).unwrap();
+        assert_eq!((grid.width(), grid.height()), (4, 4));
+        assert_eq!(grid.to_string(), "1200\n3456\n0000\n7000\n");
+        let error = Grid::parse_ragged("12\n3x", "a digit", 0, |c| c.to_digit(10)).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+    }
+
+    #[test]
+    fn neighbours_in_reading_order() {
+        let grid = Grid::new(3, 3, ());
+        assert_eq!(
+            grid.neighbours4((1, 1)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1), (2, 1), (1, 2)]
+        );
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1)]
+        );
+        assert_eq!(
+            grid.neighbours8((2, 2)).collect::<Vec<_>>(),
+            [(1, 1), (2, 1), (1, 2)]
+        );
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn positions_in_reading_order() {
+        let grid = Grid::from_fn(2, 2, |pos| pos);
+        let positions: Vec<_> = grid.positions().collect();
+        assert_eq!(positions, [(0, 0), (1, 0), (0, 1), (1, 1)]);
+        assert!(grid.iter().all(|(pos, &cell)| pos == cell));
+        let mut sorted = positions.clone();
+        sorted.sort_by_key(|&pos| reading_order(pos));
+        assert_eq!(sorted, positions);
+    }
+}
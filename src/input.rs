@@ -0,0 +1,15 @@
+use std::{
+    fs,
+    io::{self, Read},
+};
+
+/// Reads puzzle input from `path`, or from stdin if `path` is `-`.
+pub fn read(path: &str) -> io::Result<String> {
+    if path == "-" {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        Ok(input)
+    } else {
+        fs::read_to_string(path)
+    }
+}
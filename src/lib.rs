@@ -0,0 +1,105 @@
+use answer::{Answer, Fact};
+use params::{Param, Params};
+use registry::Part;
+
+pub mod answer;
+pub mod bench;
+pub mod cycle;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod differential;
+pub mod elfcode;
+pub mod generate;
+pub mod grid;
+pub mod input;
+mod iter;
+pub mod observe;
+pub mod parallel;
+pub mod params;
+pub mod parse;
+pub mod profile;
+pub mod registry;
+pub mod snapshot;
+pub mod step;
+pub mod verify;
+pub mod visualize;
+mod worker;
+
+/// A day's puzzle: parse the input once, then answer both parts from it.
+pub trait Solution {
+    /// The day of the advent calendar this solves.
+    const DAY: u32;
+    /// The puzzle input bundled with the crate, used when no other input is
+    /// given.
+    const INPUT: &'static str;
+    /// The answers for the bundled input, in the format read by
+    /// [`verify::Answers::parse`].
+    const ANSWERS: &'static str;
+
+    /// The numbers the solution depends on besides its input, which
+    /// [`Solution::parse`] gives their defaults.
+    const PARAMS: &'static [Param] = &[];
+
+    type Input;
+    type Part1: Into<Answer>;
+    type Part2: Into<Answer>;
+
+    fn parse(input: &str) -> parse::Result<Self::Input>;
+    /// Sets parsed input's parameters to `params`.
+    fn configure(_input: &mut Self::Input, _params: &Params) {}
+    fn part1(input: &Self::Input) -> Self::Part1;
+    fn part2(input: &Self::Input) -> Self::Part2;
+
+    /// The intermediate values behind `part`'s answer. Most days have
+    /// nothing worth showing beyond the answer itself.
+    fn explain(_input: &Self::Input, _part: Part) -> Vec<Fact> {
+        Vec::new()
+    }
+}
+
+/// A day whose state can be drawn, frame by frame.
+pub trait Render: Solution {
+    const STYLE: visualize::Style;
+
+    fn frames(input: &Self::Input) -> Box<dyn Iterator<Item = visualize::Frame> + '_>;
+}
+
+/// A day whose state is a simulation that can be stepped through.
+pub trait Simulate: Render {
+    type Simulation: visualize::Simulation;
+
+    fn simulation(input: &Self::Input) -> Self::Simulation;
+}
+
+/// A day with a part that takes long enough to be worth saving partway
+/// through, so that [`snapshot::run`] can carry on from where it stopped.
+pub trait Resume: Solution {
+    /// Everything that changes as the part runs.
+    type State: serde::Serialize + serde::de::DeserializeOwned;
+    /// Everything the part needs that doesn't change, so isn't saved.
+    type Context;
+
+    /// Where `part` starts from, or `None` if it can't be resumed.
+    fn start(input: &Self::Input, part: Part) -> Option<(Self::State, Self::Context)>;
+    /// Does the next step, returning `false` once there are no more.
+    fn advance(state: &mut Self::State, context: &Self::Context) -> bool;
+    /// The answer from a state with no more steps.
+    fn finish(state: &Self::State, context: &Self::Context) -> Answer;
+}
@@ -0,0 +1,24 @@
+/// Something told about each event in a simulation as it happens, to log,
+/// count or check them without touching the simulation itself.
+///
+/// `()` ignores every event, a `Vec` keeps them all, and a closure is called
+/// with each one.
+pub trait Observer<E> {
+    fn notify(&mut self, event: &E);
+}
+
+impl<E> Observer<E> for () {
+    fn notify(&mut self, _: &E) {}
+}
+
+impl<E: Clone> Observer<E> for Vec<E> {
+    fn notify(&mut self, event: &E) {
+        self.push(event.clone());
+    }
+}
+
+impl<E, F: FnMut(&E)> Observer<E> for F {
+    fn notify(&mut self, event: &E) {
+        self(event)
+    }
+}
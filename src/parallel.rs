@@ -0,0 +1,231 @@
+use crate::{
+    parse::ParseError,
+    registry::{Part, Puzzle},
+    verify::{abbreviate, normalize},
+    worker::{self, Failure},
+};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::Sender,
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+/// How a day ended when run with [`run_all`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Done,
+    Invalid(ParseError),
+    /// The day panicked, with the panic's message.
+    Panicked(String),
+    TimedOut,
+}
+
+/// What running one day found, up to the point it stopped.
+#[derive(Clone, Debug)]
+pub struct Report {
+    pub day: u32,
+    pub parse: Option<Duration>,
+    pub answers: Vec<(Part, String, Duration)>,
+    pub outcome: Outcome,
+}
+
+impl Report {
+    pub fn is_ok(&self) -> bool {
+        self.outcome == Outcome::Done
+    }
+
+    /// How long parsing and the parts that finished took.
+    pub fn elapsed(&self) -> Duration {
+        self.parse.unwrap_or_default() + self.answers.iter().map(|&(_, _, time)| time).sum()
+    }
+
+    /// This day's row in the summary table.
+    pub fn row(&self) -> String {
+        let answer = |part| {
+            self.answers
+                .iter()
+                .find(|&&(p, _, _)| p == part)
+                .map_or_else(|| "-".to_string(), |(_, answer, _)| abbreviate(answer))
+        };
+        let status = match &self.outcome {
+            Outcome::Done => "ok".to_string(),
+            Outcome::Invalid(error) => format!("invalid input: {}", error),
+            Outcome::Panicked(message) => format!("PANICKED: {}", message),
+            Outcome::TimedOut => "TIMED OUT".to_string(),
+        };
+        format!(
+            "| {} | {} | {} | {:.1} | {} |",
+            self.day,
+            answer(Part::One),
+            answer(Part::Two),
+            self.elapsed().as_secs_f64() * 1000.0,
+            status
+        )
+    }
+}
+
+pub const TABLE_HEADER: &str = "\
+| Day | Part 1 | Part 2 | Time (ms) | Status |
+| --: | -----: | -----: | --------: | ------ |";
+
+enum Event {
+    Parsed(Duration),
+    Solved(Part, String, Duration),
+    Invalid(ParseError),
+}
+
+/// Solves each puzzle with its input on a pool of `threads` threads, giving
+/// up on a day that takes longer than `timeout` altogether.
+///
+/// Each day runs under [`worker::collect_until`], so a panicking day is
+/// reported without disturbing the others, and a day that times out is left
+/// to finish in the background while its pool thread moves on.
+pub fn run_all(
+    jobs: Vec<(&'static Puzzle, String)>,
+    threads: usize,
+    timeout: Duration,
+) -> Vec<Report> {
+    let next = AtomicUsize::new(0);
+    let reports = Mutex::new(vec![None; jobs.len()]);
+    thread::scope(|scope| {
+        for _ in 0..threads.max(1) {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::SeqCst);
+                let (puzzle, input) = match jobs.get(i) {
+                    Some(job) => job.clone(),
+                    None => return,
+                };
+                let report = run_one(puzzle, input, timeout);
+                reports.lock().unwrap()[i] = Some(report);
+            });
+        }
+    });
+    reports
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|report| report.expect("every job is taken"))
+        .collect()
+}
+
+fn run_one(puzzle: &'static Puzzle, input: String, timeout: Duration) -> Report {
+    let (events, failure) = worker::collect_until(Instant::now() + timeout, move |tx| {
+        solve(puzzle, &input, &tx)
+    });
+    let mut report = Report {
+        day: puzzle.day,
+        parse: None,
+        answers: Vec::new(),
+        outcome: match failure {
+            None => Outcome::Done,
+            Some(Failure::TimedOut) => Outcome::TimedOut,
+            Some(Failure::Panicked(message)) => Outcome::Panicked(message),
+        },
+    };
+    for event in events {
+        match event {
+            Event::Parsed(time) => report.parse = Some(time),
+            Event::Solved(part, answer, time) => report.answers.push((part, answer, time)),
+            Event::Invalid(error) => report.outcome = Outcome::Invalid(error),
+        }
+    }
+    report
+}
+
+fn solve(puzzle: &Puzzle, input: &str, tx: &Sender<Event>) {
+    let start = Instant::now();
+    let parsed = match puzzle.parse(input) {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            let _ = tx.send(Event::Invalid(error));
+            return;
+        }
+    };
+    let _ = tx.send(Event::Parsed(start.elapsed()));
+    for &part in &Part::BOTH {
+        let start = Instant::now();
+        let answer = normalize(&puzzle.solve(&*parsed, part).to_string());
+        if tx
+            .send(Event::Solved(part, answer, start.elapsed()))
+            .is_err()
+        {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, registry, Solution};
+
+    /// Panics on part 1 or hangs on part 2 if its input says so.
+    struct Trouble;
+
+    impl Solution for Trouble {
+        const DAY: u32 = 99;
+        const INPUT: &'static str = "";
+        const ANSWERS: &'static str = "";
+
+        type Input = String;
+        type Part1 = usize;
+        type Part2 = usize;
+
+        fn parse(input: &str) -> parse::Result<String> {
+            Ok(input.to_string())
+        }
+
+        fn part1(input: &String) -> usize {
+            assert!(input != "panic", "told to panic");
+            1
+        }
+
+        fn part2(input: &String) -> usize {
+            if input == "hang" {
+                loop {
+                    thread::sleep(Duration::from_secs(1));
+                }
+            }
+            2
+        }
+    }
+
+    static TROUBLE: Puzzle = Puzzle::new::<Trouble>();
+
+    #[test]
+    fn isolates_failures() {
+        let day01 = registry::find(1).unwrap();
+        let jobs = vec![
+            (&TROUBLE, "hang".to_string()),
+            (&TROUBLE, "panic".to_string()),
+            (day01, "+1\n-2\n+3\n".to_string()),
+            (day01, "+1\nnope\n".to_string()),
+            (&TROUBLE, "fine".to_string()),
+        ];
+        let reports = run_all(jobs, 2, Duration::from_millis(500));
+        let outcomes: Vec<_> = reports.iter().map(|r| r.outcome.clone()).collect();
+        assert_eq!(outcomes[0], Outcome::TimedOut);
+        assert_eq!(outcomes[1], Outcome::Panicked("told to panic".to_string()));
+        assert_eq!(outcomes[2], Outcome::Done);
+        assert!(matches!(
+            outcomes[3],
+            Outcome::Invalid(ParseError { line: 2, .. })
+        ));
+        assert_eq!(outcomes[4], Outcome::Done);
+
+        let answers = |i: usize| -> Vec<_> {
+            reports[i]
+                .answers
+                .iter()
+                .map(|(_, answer, _)| answer.as_str())
+                .collect()
+        };
+        assert_eq!(answers(0), ["1"]);
+        assert_eq!(answers(2), ["2", "1"]);
+        assert!(reports[1].row().ends_with("| PANICKED: told to panic |"));
+    }
+}
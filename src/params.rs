@@ -0,0 +1,219 @@
+use crate::parse::{ParseError, Result};
+use std::collections::HashMap;
+
+/// A number a day's solution depends on that isn't part of the puzzle
+/// input, like how many workers there are.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    pub doc: &'static str,
+    pub default: u64,
+    /// The smallest value that makes sense.
+    pub min: u64,
+    /// The largest.
+    pub max: u64,
+}
+
+/// The values of a day's parameters: the defaults, except where overridden.
+#[derive(Clone, Debug, Default)]
+pub struct Params(HashMap<&'static str, u64>);
+
+impl Params {
+    /// The defaults, except for `values`.
+    pub fn with(values: &[(Param, u64)]) -> Self {
+        Params(
+            values
+                .iter()
+                .map(|(param, value)| (param.name, *value))
+                .collect(),
+        )
+    }
+
+    /// The value of `param`, kept within its range.
+    pub fn get(&self, param: &Param) -> u64 {
+        let value = self.0.get(param.name).copied().unwrap_or(param.default);
+        value.clamp(param.min, param.max)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Override {
+    /// The day it's for, or `None` for any day with a parameter of that
+    /// name.
+    day: Option<u32>,
+    name: String,
+    value: u64,
+}
+
+impl Override {
+    fn applies_to(&self, day: u32) -> bool {
+        self.day.is_none_or(|d| d == day)
+    }
+}
+
+/// Parameter values given on the command line or in a config file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Overrides(Vec<Override>);
+
+impl Overrides {
+    /// Adds an override written like `workers=2` or `day07.workers=2`.
+    pub fn set(&mut self, assignment: &str) -> std::result::Result<(), String> {
+        let invalid = || {
+            format!(
+                "expected `name=value` or `dayNN.name=value`, got `{}`",
+                assignment
+            )
+        };
+        let (name, value) = assignment.split_once('=').ok_or_else(invalid)?;
+        let (day, name) = match name.trim().split_once('.') {
+            Some((day, name)) => {
+                let day = day.strip_prefix("day").and_then(|day| day.parse().ok());
+                (Some(day.ok_or_else(invalid)?), name)
+            }
+            None => (None, name.trim()),
+        };
+        let value = value.trim().parse().map_err(|_| invalid())?;
+        self.0.push(Override {
+            day,
+            name: name.to_string(),
+            value,
+        });
+        Ok(())
+    }
+
+    /// Reads overrides from a config file, with each day's parameters under
+    /// a heading like `[day07]`, one `name = value` per line. Blank lines and
+    /// lines starting with `#` are ignored.
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut overrides = Overrides::default();
+        let mut day = None;
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            let error = |expected: &str| ParseError {
+                line: i + 1,
+                column: 1,
+                expected: expected.to_string(),
+                found: format!("`{}`", line),
+            };
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(heading) = line.strip_prefix("[day").and_then(|h| h.strip_suffix(']')) {
+                day = Some(
+                    heading
+                        .parse()
+                        .map_err(|_| error("a heading like `[day07]`"))?,
+                );
+                continue;
+            }
+            let day = day.ok_or_else(|| error("a heading like `[day07]`"))?;
+            let (name, value) = line
+                .split_once('=')
+                .ok_or_else(|| error("`name = value`"))?;
+            overrides.0.push(Override {
+                day: Some(day),
+                name: name.trim().to_string(),
+                value: value.trim().parse().map_err(|_| error("`name = number`"))?,
+            });
+        }
+        Ok(overrides)
+    }
+
+    /// Adds `other`'s overrides after these, so that they win.
+    pub fn extend(&mut self, other: Overrides) {
+        self.0.extend(other.0);
+    }
+
+    /// Checks that every override names a parameter of one of `days`, and
+    /// is within its range.
+    pub fn check(&self, days: &[(u32, &[Param])]) -> std::result::Result<(), String> {
+        for o in &self.0 {
+            let params = days
+                .iter()
+                .filter(|&&(day, _)| o.applies_to(day))
+                .filter_map(|(_, params)| params.iter().find(|p| p.name == o.name));
+            let mut found = false;
+            for param in params {
+                found = true;
+                if o.value < param.min {
+                    return Err(format!(
+                        "`{}` can't be less than {}, got {}",
+                        o.name, param.min, o.value
+                    ));
+                }
+                if o.value > param.max {
+                    return Err(format!(
+                        "`{}` can't be more than {}, got {}",
+                        o.name, param.max, o.value
+                    ));
+                }
+            }
+            if !found {
+                return Err(match o.day {
+                    Some(day) => format!("day {} has no parameter `{}`", day, o.name),
+                    None => format!("no day being run has a parameter `{}`", o.name),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// The values of `day`'s parameters, with the last override of each
+    /// winning.
+    pub fn for_day(&self, day: u32, params: &'static [Param]) -> Params {
+        let mut values = HashMap::new();
+        for o in &self.0 {
+            if o.applies_to(day) {
+                if let Some(param) = params.iter().find(|p| p.name == o.name) {
+                    values.insert(param.name, o.value);
+                }
+            }
+        }
+        Params(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORKERS: Param = Param {
+        name: "workers",
+        doc: "",
+        default: 5,
+        min: 1,
+        max: 26,
+    };
+
+    #[test]
+    fn overrides() {
+        let mut overrides = Overrides::parse("# example\n[day07]\nworkers = 2\n").unwrap();
+        assert_eq!(overrides.for_day(7, &[WORKERS]).get(&WORKERS), 2);
+        assert_eq!(overrides.for_day(8, &[WORKERS]).get(&WORKERS), 5);
+        overrides.set("workers=3").unwrap();
+        assert_eq!(overrides.for_day(7, &[WORKERS]).get(&WORKERS), 3);
+
+        assert!(overrides.check(&[(7, &[WORKERS])]).is_ok());
+        assert_eq!(
+            overrides.check(&[(8, &[])]).unwrap_err(),
+            "day 7 has no parameter `workers`"
+        );
+        overrides.set("day07.workers=0").unwrap();
+        assert_eq!(
+            overrides.check(&[(7, &[WORKERS])]).unwrap_err(),
+            "`workers` can't be less than 1, got 0"
+        );
+        let mut too_many = Overrides::default();
+        too_many.set("workers=27").unwrap();
+        assert_eq!(
+            too_many.check(&[(7, &[WORKERS])]).unwrap_err(),
+            "`workers` can't be more than 26, got 27"
+        );
+        assert_eq!(too_many.for_day(7, &[WORKERS]).get(&WORKERS), 26);
+        assert!(overrides.set("workers").is_err());
+        assert_eq!(
+            Overrides::parse("workers = 2").unwrap_err().to_string(),
+            "line 1, column 1: expected a heading like `[day07]`, found `workers = 2`"
+        );
+    }
+}
@@ -0,0 +1,255 @@
+use std::{error::Error, fmt, iter, str::FromStr};
+
+/// Where and why a puzzle input couldn't be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number; one past the last line if the input ended early.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    /// What the parser was looking for, e.g. "a number" or "`@`".
+    pub expected: String,
+    /// What it found instead.
+    pub found: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found {}",
+            self.line, self.column, self.expected, self.found
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+pub type Result<T> = std::result::Result<T, ParseError>;
+
+/// Splits `input` into numbered lines to scan.
+pub fn lines(input: &str) -> Lines<'_> {
+    Lines {
+        lines: input.lines(),
+        number: 0,
+    }
+}
+
+pub struct Lines<'a> {
+    lines: std::str::Lines<'a>,
+    number: usize,
+}
+
+impl<'a> Lines<'a> {
+    /// The next line, or an error at the end of the input if there isn't one.
+    pub fn expect(&mut self, expected: &str) -> Result<Line<'a>> {
+        let end = self.number + 1;
+        self.next().ok_or_else(|| ParseError {
+            line: end,
+            column: 1,
+            expected: expected.to_string(),
+            found: "end of input".to_string(),
+        })
+    }
+
+    /// Every line left, or an error at the end of the input if there are
+    /// none.
+    pub fn at_least_one(mut self, expected: &str) -> Result<impl Iterator<Item = Line<'a>>> {
+        let first = self.expect(expected)?;
+        Ok(iter::once(first).chain(self))
+    }
+}
+
+/// An error at the end of `input`, for something that should have been
+/// somewhere in it but wasn't.
+pub fn missing(input: &str, expected: &str) -> ParseError {
+    ParseError {
+        line: input.lines().count() + 1,
+        column: 1,
+        expected: expected.to_string(),
+        found: "end of input".to_string(),
+    }
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = Line<'a>;
+
+    fn next(&mut self) -> Option<Line<'a>> {
+        let text = self.lines.next()?;
+        self.number += 1;
+        Some(Line {
+            number: self.number,
+            text,
+            offset: 0,
+        })
+    }
+}
+
+/// A cursor over one line of input.
+#[derive(Clone, Copy, Debug)]
+pub struct Line<'a> {
+    pub number: usize,
+    text: &'a str,
+    offset: usize,
+}
+
+impl<'a> Line<'a> {
+    /// The part of the line that hasn't been consumed yet.
+    pub fn rest(&self) -> &'a str {
+        &self.text[self.offset..]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rest().trim().is_empty()
+    }
+
+    pub fn column(&self) -> usize {
+        self.text[..self.offset].chars().count() + 1
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    /// Consumes and returns the next character.
+    pub fn next_char(&mut self, expected: &str) -> Result<char> {
+        let c = self.peek().ok_or_else(|| self.error(expected))?;
+        self.offset += c.len_utf8();
+        Ok(c)
+    }
+
+    /// An error at the current position, saying what was there instead.
+    pub fn error(&self, expected: &str) -> ParseError {
+        let found = match self.peek() {
+            Some(c) => format!("`{}`", c),
+            None => "end of line".to_string(),
+        };
+        ParseError {
+            line: self.number,
+            column: self.column(),
+            expected: expected.to_string(),
+            found,
+        }
+    }
+
+    pub fn skip_spaces(&mut self) {
+        let rest = self.rest();
+        self.offset += rest.len() - rest.trim_start_matches(' ').len();
+    }
+
+    /// Consumes `literal`, after any spaces.
+    pub fn literal(&mut self, literal: &str) -> Result<()> {
+        self.skip_spaces();
+        if self.rest().starts_with(literal) {
+            self.offset += literal.len();
+            Ok(())
+        } else {
+            Err(self.error(&format!("`{}`", literal)))
+        }
+    }
+
+    /// Consumes a run of ASCII letters, after any spaces.
+    pub fn word(&mut self) -> &'a str {
+        self.skip_spaces();
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        self.offset += len;
+        &rest[..len]
+    }
+
+    /// Consumes an optionally signed decimal number, after any spaces.
+    pub fn number<T: FromStr>(&mut self) -> Result<T> {
+        self.skip_spaces();
+        let rest = self.rest();
+        let sign = if rest.starts_with(['+', '-']) { 1 } else { 0 };
+        let len = rest[sign..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len() - sign);
+        match rest[..sign + len].parse() {
+            Ok(n) if len > 0 => {
+                self.offset += sign + len;
+                Ok(n)
+            }
+            _ => Err(self.error("a number")),
+        }
+    }
+
+    /// Checks that nothing but trailing whitespace is left.
+    pub fn end(&mut self) -> Result<()> {
+        self.skip_spaces();
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error("end of line"))
+        }
+    }
+
+    /// Maps every remaining character with `cell`, which returns `None` for a
+    /// character that isn't one of `expected`.
+    pub fn cells<T>(
+        &mut self,
+        expected: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Vec<T>> {
+        let mut cells = Vec::new();
+        while let Some(c) = self.peek() {
+            cells.push(cell(c).ok_or_else(|| self.error(expected))?);
+            self.offset += c.len_utf8();
+        }
+        Ok(cells)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scans_a_line() {
+        let mut line = lines("#12 @ -3,+4").next().unwrap();
+        line.literal("#").unwrap();
+        assert_eq!(line.number::<u32>(), Ok(12));
+        line.literal("@").unwrap();
+        assert_eq!(line.number::<i32>(), Ok(-3));
+        line.literal(",").unwrap();
+        assert_eq!(line.number::<i32>(), Ok(4));
+        assert_eq!(line.end(), Ok(()));
+    }
+
+    #[test]
+    fn reports_position() {
+        let mut lines = lines("1\n2 x");
+        lines.next();
+        let mut line = lines.next().unwrap();
+        line.number::<u32>().unwrap();
+        let error = line.number::<u32>().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: expected a number, found `x`"
+        );
+        assert_eq!(
+            lines.expect("another line").unwrap_err().to_string(),
+            "line 3, column 1: expected another line, found end of input"
+        );
+    }
+
+    #[test]
+    fn reports_missing_input() {
+        let error = lines("").at_least_one("a line").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 1: expected a line, found end of input"
+        );
+        assert_eq!(lines("a\nb").at_least_one("a line").unwrap().count(), 2);
+        assert_eq!(missing("a\nb\n", "a cart").line, 3);
+    }
+
+    #[test]
+    fn rejects_signs_without_digits() {
+        let mut line = lines("-").next().unwrap();
+        let error = line.number::<i32>().unwrap_err();
+        assert_eq!((error.column, error.found.as_str()), (1, "`-`"));
+    }
+}
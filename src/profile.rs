@@ -0,0 +1,219 @@
+use crate::{
+    parse::ParseError,
+    registry::{Part, Puzzle},
+    verify::{self, abbreviate, Answers, Check},
+    worker::Failure,
+};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// One account's puzzle inputs, kept in `<root>/<name>/dayNN.txt`, with the
+/// expected answers, where they're known, in `dayNN.answers.txt` beside them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Profile {
+    pub name: String,
+    dir: PathBuf,
+}
+
+impl Profile {
+    pub fn new(root: &Path, name: &str) -> Self {
+        Profile {
+            name: name.to_string(),
+            dir: root.join(name),
+        }
+    }
+
+    /// Every profile under `root`, in name order.
+    pub fn all(root: &Path) -> io::Result<Vec<Profile>> {
+        let mut profiles = Vec::new();
+        for entry in fs::read_dir(root)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                profiles.push(Profile {
+                    name: entry.file_name().to_string_lossy().into_owned(),
+                    dir: entry.path(),
+                });
+            }
+        }
+        profiles.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(profiles)
+    }
+
+    /// The profile's input for `day`, if it has one.
+    pub fn input(&self, day: u32) -> io::Result<Option<String>> {
+        read_if_present(&self.dir.join(format!("day{:02}.txt", day)))
+    }
+
+    /// The expected answers for the profile's input for `day`; empty if
+    /// there are none.
+    pub fn answers(&self, day: u32) -> io::Result<Answers> {
+        let answers = read_if_present(&self.dir.join(format!("day{:02}.answers.txt", day)))?;
+        Ok(answers.map_or_else(Answers::default, |text| Answers::parse(&text)))
+    }
+}
+
+fn read_if_present(path: &Path) -> io::Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(Some(text)),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+/// What became of one profile's input for a day.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Run {
+    /// The profile has no input for the day.
+    Missing,
+    Invalid(ParseError),
+    Checked(Vec<(Part, Check)>),
+}
+
+impl Run {
+    fn cell(&self, part: Part) -> String {
+        let check = match self {
+            Run::Missing => return "-".to_string(),
+            Run::Invalid(_) => return "INVALID INPUT".to_string(),
+            Run::Checked(checks) => checks.iter().find(|&&(p, _)| p == part).map(|(_, c)| c),
+        };
+        match check {
+            Some(Check::Correct(answer)) | Some(Check::Unknown(answer)) => abbreviate(answer),
+            Some(Check::Wrong { actual, .. }) => format!("{} (WRONG)", abbreviate(actual)),
+            Some(Check::Failed(Failure::TimedOut)) => "TIMED OUT".to_string(),
+            Some(Check::Failed(Failure::Panicked(_))) => "PANICKED".to_string(),
+            Some(Check::Skipped) | None => "skipped".to_string(),
+        }
+    }
+}
+
+/// How every profile fared on one day.
+#[derive(Debug)]
+pub struct DayRuns {
+    pub day: u32,
+    pub runs: Vec<(String, Run)>,
+}
+
+impl DayRuns {
+    /// This day's rows in the answer matrix, one per part.
+    pub fn rows(&self) -> String {
+        let rows: Vec<_> = Part::BOTH
+            .iter()
+            .map(|&part| {
+                let cells: Vec<_> = self.runs.iter().map(|(_, run)| run.cell(part)).collect();
+                format!("| {} | {} | {} |", self.day, part, cells.join(" | "))
+            })
+            .collect();
+        rows.join("\n")
+    }
+
+    /// The profiles whose input was invalid, or whose solver panicked, timed
+    /// out or gave a wrong answer, with what went wrong.
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        for (name, run) in &self.runs {
+            match run {
+                Run::Missing => {}
+                Run::Invalid(error) => problems.push(format!(
+                    "{} day {:02}: invalid input: {}",
+                    name, self.day, error
+                )),
+                Run::Checked(checks) => {
+                    for (part, check) in checks {
+                        if !check.is_ok() && *check != Check::Skipped {
+                            problems.push(format!(
+                                "{} day {:02} part {}: {}",
+                                name, self.day, part, check
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+        problems
+    }
+}
+
+/// The header of the answer matrix, with a column for each profile.
+pub fn table_header(profiles: &[Profile]) -> String {
+    let names: Vec<_> = profiles.iter().map(|p| p.name.as_str()).collect();
+    let rules: Vec<_> = profiles
+        .iter()
+        .map(|p| "-".repeat(p.name.len().max(3)))
+        .collect();
+    format!(
+        "| Day | Part | {} |\n| --: | ---: | {} |",
+        names.join(" | "),
+        rules.join(" | ")
+    )
+}
+
+/// Solves `puzzle` for every profile that has an input for it, giving up on
+/// a part that takes longer than `timeout`.
+pub fn run(
+    puzzle: &'static Puzzle,
+    profiles: &[Profile],
+    timeout: Duration,
+) -> io::Result<DayRuns> {
+    let mut runs = Vec::new();
+    for profile in profiles {
+        let run = match profile.input(puzzle.day)? {
+            None => Run::Missing,
+            Some(input) => {
+                let answers = profile.answers(puzzle.day)?;
+                match verify::verify(puzzle, input, &answers, timeout) {
+                    Ok(checks) => Run::Checked(checks),
+                    Err(error) => Run::Invalid(error),
+                }
+            }
+        };
+        runs.push((profile.name.clone(), run));
+    }
+    Ok(DayRuns {
+        day: puzzle.day,
+        runs,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+    use std::process;
+
+    #[test]
+    fn runs_every_profile() {
+        let root = std::env::temp_dir().join(format!("aoc-profiles-{}", process::id()));
+        let write = |path: &str, text: &str| {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, text).unwrap();
+        };
+        write("bob/day01.txt", "+1\n+1\nnope\n");
+        write("carol/day01.txt", "+3\n+3\n+4\n-2\n-4\n");
+        write("carol/day01.answers.txt", "[part1]\n4\n[part2]\n11\n");
+        write("alice/day02.txt", "abcdef\n");
+
+        let profiles = Profile::all(&root).unwrap();
+        let names: Vec<_> = profiles.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["alice", "bob", "carol"]);
+
+        let puzzle = registry::find(1).unwrap();
+        let day = run(puzzle, &profiles, Duration::from_secs(10)).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(
+            day.rows(),
+            "| 1 | 1 | - | INVALID INPUT | 4 |\n| 1 | 2 | - | INVALID INPUT | 10 (WRONG) |"
+        );
+        let problems = day.problems();
+        assert_eq!(problems.len(), 2);
+        assert!(problems[0].starts_with("bob day 01: invalid input: line 3, column 1"));
+        assert_eq!(
+            problems[1],
+            "carol day 01 part 2: WRONG, expected 11 but got 10"
+        );
+    }
+}
@@ -0,0 +1,324 @@
+use crate::{
+    answer::{Answer, Fact},
+    day01::Day01,
+    day02::Day02,
+    day03::Day03,
+    day04::Day04,
+    day05::Day05,
+    day06::Day06,
+    day07::Day07,
+    day08::Day08,
+    day09::Day09,
+    day10::Day10,
+    day11::Day11,
+    day12::Day12,
+    day13::Day13,
+    day14::Day14,
+    day15::Day15,
+    day16::Day16,
+    day17::Day17,
+    day18::Day18,
+    day19::Day19,
+    input,
+    params::{Param, Params},
+    parse, snapshot, step,
+    verify::Answers,
+    visualize, Render, Resume, Simulate, Solution,
+};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    any::Any,
+    fmt,
+    io::{self, Write},
+    time::Duration,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Serialize for Part {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Part::One => serializer.serialize_u8(1),
+            Part::Two => serializer.serialize_u8(2),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Part {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match u8::deserialize(deserializer)? {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            n => Err(de::Error::invalid_value(
+                de::Unexpected::Unsigned(n.into()),
+                &"1 or 2",
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::One => '1'.fmt(f),
+            Part::Two => '2'.fmt(f),
+        }
+    }
+}
+
+type RenderFn = fn(&dyn Any, &mut dyn Write, &visualize::Options) -> io::Result<()>;
+type StepFn = fn(&dyn Any, Option<Duration>) -> io::Result<()>;
+type ResumeFn = fn(&dyn Any, u64, Part, &snapshot::Options) -> io::Result<Option<Answer>>;
+
+/// A day's solution with its types erased, so that days can be picked at
+/// runtime.
+pub struct Puzzle {
+    pub day: u32,
+    pub params: &'static [Param],
+    bundled_input: &'static str,
+    bundled_answers: &'static str,
+    parse: fn(&str) -> parse::Result<Box<dyn Any>>,
+    configure: fn(&mut dyn Any, &Params),
+    part1: fn(&dyn Any) -> Answer,
+    part2: fn(&dyn Any) -> Answer,
+    explain: fn(&dyn Any, Part) -> Vec<Fact>,
+    render: Option<RenderFn>,
+    step: Option<StepFn>,
+    resume: Option<ResumeFn>,
+}
+
+impl Puzzle {
+    pub const fn new<S: Solution>() -> Self
+    where
+        S::Input: 'static,
+    {
+        Puzzle {
+            day: S::DAY,
+            params: S::PARAMS,
+            bundled_input: S::INPUT,
+            bundled_answers: S::ANSWERS,
+            parse: parse::<S>,
+            configure: configure::<S>,
+            part1: part1::<S>,
+            part2: part2::<S>,
+            explain: explain::<S>,
+            render: None,
+            step: None,
+            resume: None,
+        }
+    }
+
+    const fn with_render<S: Render>() -> Self
+    where
+        S::Input: 'static,
+    {
+        Puzzle {
+            render: Some(render::<S>),
+            ..Puzzle::new::<S>()
+        }
+    }
+
+    const fn with_simulation<S: Simulate>() -> Self
+    where
+        S::Input: 'static,
+    {
+        Puzzle {
+            step: Some(step::<S>),
+            ..Puzzle::with_render::<S>()
+        }
+    }
+
+    const fn resumable<S: Resume>(self) -> Self
+    where
+        S::Input: 'static,
+    {
+        Puzzle {
+            resume: Some(resume::<S>),
+            ..self
+        }
+    }
+
+    /// Reads the input at `path`, or the bundled input if there is none.
+    pub fn input(&self, path: Option<&str>) -> io::Result<String> {
+        match path {
+            Some(path) => input::read(path),
+            None => Ok(self.bundled_input.to_string()),
+        }
+    }
+
+    /// Reads the expected answers at `path`, or the answers for the bundled
+    /// input if there is none.
+    pub fn answers(&self, path: Option<&str>) -> io::Result<Answers> {
+        match path {
+            Some(path) => Ok(Answers::parse(&input::read(path)?)),
+            None => Ok(Answers::parse(self.bundled_answers)),
+        }
+    }
+
+    pub fn parse(&self, input: &str) -> parse::Result<Box<dyn Any>> {
+        (self.parse)(input)
+    }
+
+    /// Like [`Puzzle::parse`], with the day's parameters set to `params`.
+    pub fn parse_with(&self, input: &str, params: &Params) -> parse::Result<Box<dyn Any>> {
+        let mut parsed = (self.parse)(input)?;
+        (self.configure)(&mut *parsed, params);
+        Ok(parsed)
+    }
+
+    /// Answers one part from input returned by [`Puzzle::parse`].
+    pub fn solve(&self, input: &dyn Any, part: Part) -> Answer {
+        match part {
+            Part::One => (self.part1)(input),
+            Part::Two => (self.part2)(input),
+        }
+    }
+
+    /// The intermediate values behind one part's answer, if the day shows
+    /// any.
+    pub fn explain(&self, input: &dyn Any, part: Part) -> Vec<Fact> {
+        (self.explain)(input, part)
+    }
+
+    pub fn can_render(&self) -> bool {
+        self.render.is_some()
+    }
+
+    /// Draws the day's state to `out`, if it has a visualization.
+    pub fn render(
+        &self,
+        input: &dyn Any,
+        out: &mut dyn Write,
+        options: &visualize::Options,
+    ) -> Option<io::Result<()>> {
+        self.render.map(|render| render(input, out, options))
+    }
+
+    pub fn can_step(&self) -> bool {
+        self.step.is_some()
+    }
+
+    /// Steps through the day's simulation in the terminal, if it has one,
+    /// playing it with `delay` between steps rather than the day's own.
+    pub fn step(&self, input: &dyn Any, delay: Option<Duration>) -> Option<io::Result<()>> {
+        self.step.map(|step| step(input, delay))
+    }
+
+    /// Answers one part like [`Puzzle::solve`], but through
+    /// [`snapshot::run`], saving its progress and carrying on from an earlier
+    /// run's. `fingerprint` identifies the input and parameters. Returns
+    /// `None` if the part can't be resumed.
+    pub fn resume(
+        &self,
+        input: &dyn Any,
+        fingerprint: u64,
+        part: Part,
+        options: &snapshot::Options,
+    ) -> io::Result<Option<Answer>> {
+        match self.resume {
+            Some(resume) => resume(input, fingerprint, part, options),
+            None => Ok(None),
+        }
+    }
+}
+
+fn parse<S: Solution>(input: &str) -> parse::Result<Box<dyn Any>>
+where
+    S::Input: 'static,
+{
+    Ok(Box::new(S::parse(input)?))
+}
+
+fn configure<S: Solution>(input: &mut dyn Any, params: &Params)
+where
+    S::Input: 'static,
+{
+    S::configure(input.downcast_mut().unwrap(), params)
+}
+
+fn part1<S: Solution>(input: &dyn Any) -> Answer
+where
+    S::Input: 'static,
+{
+    S::part1(input.downcast_ref().unwrap()).into()
+}
+
+fn part2<S: Solution>(input: &dyn Any) -> Answer
+where
+    S::Input: 'static,
+{
+    S::part2(input.downcast_ref().unwrap()).into()
+}
+
+fn explain<S: Solution>(input: &dyn Any, part: Part) -> Vec<Fact>
+where
+    S::Input: 'static,
+{
+    S::explain(input.downcast_ref().unwrap(), part)
+}
+
+fn render<S: Render>(
+    input: &dyn Any,
+    out: &mut dyn Write,
+    options: &visualize::Options,
+) -> io::Result<()>
+where
+    S::Input: 'static,
+{
+    let frames = S::frames(input.downcast_ref().unwrap());
+    visualize::write(out, &S::STYLE, frames, options)
+}
+
+fn step<S: Simulate>(input: &dyn Any, delay: Option<Duration>) -> io::Result<()>
+where
+    S::Input: 'static,
+{
+    let simulation = S::simulation(input.downcast_ref().unwrap());
+    step::interact(simulation, &S::STYLE, delay.unwrap_or(S::STYLE.delay))
+}
+
+fn resume<S: Resume>(
+    input: &dyn Any,
+    fingerprint: u64,
+    part: Part,
+    options: &snapshot::Options,
+) -> io::Result<Option<Answer>>
+where
+    S::Input: 'static,
+{
+    snapshot::run::<S>(input.downcast_ref().unwrap(), fingerprint, part, options)
+}
+
+pub static PUZZLES: [Puzzle; 19] = [
+    Puzzle::new::<Day01>(),
+    Puzzle::new::<Day02>(),
+    Puzzle::with_simulation::<Day03>(),
+    Puzzle::new::<Day04>(),
+    Puzzle::new::<Day05>(),
+    Puzzle::new::<Day06>(),
+    Puzzle::new::<Day07>(),
+    Puzzle::new::<Day08>(),
+    Puzzle::new::<Day09>().resumable::<Day09>(),
+    Puzzle::with_render::<Day10>(),
+    Puzzle::new::<Day11>(),
+    Puzzle::new::<Day12>(),
+    Puzzle::with_simulation::<Day13>(),
+    Puzzle::new::<Day14>().resumable::<Day14>(),
+    Puzzle::with_simulation::<Day15>(),
+    Puzzle::new::<Day16>(),
+    Puzzle::with_simulation::<Day17>(),
+    Puzzle::with_simulation::<Day18>().resumable::<Day18>(),
+    Puzzle::new::<Day19>().resumable::<Day19>(),
+];
+
+pub fn find(day: u32) -> Option<&'static Puzzle> {
+    PUZZLES.iter().find(|puzzle| puzzle.day == day)
+}
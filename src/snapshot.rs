@@ -0,0 +1,192 @@
+use crate::{answer::Answer, registry::Part, Resume};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    fs,
+    io::{self, BufReader, BufWriter},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+/// Where and how often [`run`] saves its progress.
+#[derive(Clone, Debug)]
+pub struct Options {
+    /// Holds a snapshot for each day and part, named like `day09-part2.json`.
+    pub dir: PathBuf,
+    pub every: Duration,
+}
+
+impl Options {
+    fn path(&self, day: u32, part: Part) -> PathBuf {
+        self.dir.join(format!("day{:02}-part{}.json", day, part))
+    }
+}
+
+/// A day's state partway through one part, as saved to disk.
+#[derive(Serialize, Deserialize)]
+pub struct Snapshot<S> {
+    pub day: u32,
+    pub part: Part,
+    /// The [`fingerprint`] of the input and parameters the state came from.
+    pub input: u64,
+    /// How many steps the state is from the start.
+    pub steps: u64,
+    pub state: S,
+}
+
+impl<S: DeserializeOwned> Snapshot<S> {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let file = BufReader::new(fs::File::open(path)?);
+        serde_json::from_reader(file).map_err(|error| invalid_data(path, error))
+    }
+}
+
+impl<S: Serialize> Snapshot<S> {
+    /// Writes the snapshot beside `path` first and then moves it there, so
+    /// that being interrupted while saving leaves the last snapshot intact.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let partial = path.with_extension("json.partial");
+        let mut file = BufWriter::new(fs::File::create(&partial)?);
+        serde_json::to_writer(&mut file, self).map_err(|error| invalid_data(path, error))?;
+        file.into_inner()?.sync_all()?;
+        fs::rename(partial, path)
+    }
+}
+
+fn invalid_data(path: &Path, error: impl std::fmt::Display) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}: {}", path.display(), error),
+    )
+}
+
+/// A 64-bit FNV-1a hash of `texts`, to tell whether a snapshot came from the
+/// same input.
+pub fn fingerprint<'a>(texts: impl IntoIterator<Item = &'a str>) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+    for text in texts {
+        for byte in text.bytes().chain(Some(0)) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    hash
+}
+
+/// Answers `part`, saving the state to `options.dir` every `options.every`
+/// and carrying on from the snapshot already there, if there is one. The
+/// snapshot is removed once the part is answered.
+///
+/// Returns `None` if the part can't be resumed, in which case it should just
+/// be solved. A snapshot from a different input is an error rather than
+/// being overwritten.
+pub fn run<S: Resume>(
+    input: &S::Input,
+    fingerprint: u64,
+    part: Part,
+    options: &Options,
+) -> io::Result<Option<Answer>> {
+    let (mut state, context) = match S::start(input, part) {
+        Some(start) => start,
+        None => return Ok(None),
+    };
+    let path = options.path(S::DAY, part);
+    let mut steps = 0;
+    if path.exists() {
+        let snapshot = Snapshot::<S::State>::load(&path)?;
+        if (snapshot.day, snapshot.part, snapshot.input) != (S::DAY, part, fingerprint) {
+            return Err(invalid_data(
+                &path,
+                "this snapshot is from a different input or parameters; delete it to start over",
+            ));
+        }
+        eprintln!(
+            "resuming day {} part {} from step {}",
+            S::DAY,
+            part,
+            snapshot.steps
+        );
+        state = snapshot.state;
+        steps = snapshot.steps;
+    } else {
+        fs::create_dir_all(&options.dir)?;
+    }
+    let mut saved = Instant::now();
+    while S::advance(&mut state, &context) {
+        steps += 1;
+        if saved.elapsed() >= options.every {
+            Snapshot {
+                day: S::DAY,
+                part,
+                input: fingerprint,
+                steps,
+                state: &state,
+            }
+            .save(&path)?;
+            saved = Instant::now();
+        }
+    }
+    if path.exists() {
+        fs::remove_file(&path)?;
+    }
+    Ok(Some(S::finish(&state, &context)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{day09::Day09, day14::Day14, day19::Day19, Solution};
+
+    /// Runs `part` part of the way, saves it as an interrupted run would,
+    /// then resumes it.
+    fn interrupt_and_resume<S: Resume>(input: &str, part: Part, steps: u64) -> Answer {
+        let options = Options {
+            dir: std::env::temp_dir().join(format!(
+                "aoc-snapshot-{}-{}",
+                std::process::id(),
+                S::DAY
+            )),
+            every: Duration::from_secs(3600),
+        };
+        let input = S::parse(input).unwrap();
+        let (mut state, context) = S::start(&input, part).unwrap();
+        for _ in 0..steps {
+            assert!(S::advance(&mut state, &context));
+        }
+        fs::create_dir_all(&options.dir).unwrap();
+        let path = options.path(S::DAY, part);
+        Snapshot {
+            day: S::DAY,
+            part,
+            input: 1,
+            steps,
+            state,
+        }
+        .save(&path)
+        .unwrap();
+
+        let error = run::<S>(&input, 2, part, &options).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        let answer = run::<S>(&input, 1, part, &options).unwrap().unwrap();
+        assert!(!path.exists());
+        fs::remove_dir(&options.dir).unwrap();
+        answer
+    }
+
+    #[test]
+    fn resumes_where_it_stopped() {
+        let game = "10 players; last marble is worth 1618 points";
+        assert_eq!(
+            interrupt_and_resume::<Day09>(game, Part::One, 1000),
+            Day09::part1(&Day09::parse(game).unwrap()).into()
+        );
+        assert_eq!(
+            interrupt_and_resume::<Day14>("59414", Part::Two, 500),
+            Day14::part2(&Day14::parse("59414").unwrap()).into()
+        );
+        // saved while the program is still building its number
+        assert_eq!(
+            interrupt_and_resume::<Day19>(Day19::INPUT, Part::Two, 10),
+            Day19::part2(&Day19::parse(Day19::INPUT).unwrap()).into()
+        );
+    }
+}
@@ -0,0 +1,303 @@
+use crate::{
+    grid::Grid,
+    visualize::{self, Simulation, Style},
+};
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    queue,
+    terminal::{self, ClearType},
+};
+use std::{
+    io::{self, Write},
+    time::{Duration, Instant},
+};
+
+/// How many earlier states a [`Stepper`] keeps at most.
+const SNAPSHOTS: usize = 64;
+
+/// Moves a simulation back and forth, keeping copies of earlier states to
+/// replay from when going back.
+///
+/// Copies are kept every `interval` steps. Once there are too many, every
+/// other one is dropped and the interval doubles, so going back never
+/// replays more than `interval` steps however long the simulation has run.
+pub struct Stepper<S> {
+    current: S,
+    step: usize,
+    /// The states at steps 0, `interval`, `2 * interval`, ...
+    snapshots: Vec<S>,
+    interval: usize,
+    end: Option<usize>,
+}
+
+impl<S: Simulation> Stepper<S> {
+    pub fn new(start: S) -> Self {
+        Stepper {
+            snapshots: vec![start.clone()],
+            current: start,
+            step: 0,
+            interval: 1,
+            end: None,
+        }
+    }
+
+    pub fn state(&self) -> &S {
+        &self.current
+    }
+
+    /// How many steps the current state is from the start.
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    /// The last step, once the simulation has been run that far.
+    pub fn end(&self) -> Option<usize> {
+        self.end
+    }
+
+    /// Moves on `n` steps, or up to the end, returning `false` if it got
+    /// there first.
+    pub fn forward(&mut self, n: usize) -> bool {
+        for _ in 0..n {
+            if !self.current.step() {
+                self.end = Some(self.step);
+                return false;
+            }
+            self.step += 1;
+            if self.step == self.snapshots.len() * self.interval {
+                self.snapshots.push(self.current.clone());
+                if self.snapshots.len() > SNAPSHOTS {
+                    let mut i = 0;
+                    self.snapshots.retain(|_| {
+                        i += 1;
+                        i % 2 == 1
+                    });
+                    self.interval *= 2;
+                }
+            }
+        }
+        true
+    }
+
+    pub fn back(&mut self, n: usize) {
+        self.jump(self.step.saturating_sub(n));
+    }
+
+    /// Goes to step `target`, or the last step if the simulation ends before
+    /// then.
+    pub fn jump(&mut self, target: usize) {
+        if target < self.step {
+            let i = target / self.interval;
+            self.current = self.snapshots[i].clone();
+            self.step = i * self.interval;
+        }
+        self.forward(target - self.step);
+    }
+}
+
+const HELP: &str = "space play/pause  \u{2192} step  \u{2190} back  N\u{2192} N\u{2190} N steps  Ng jump  hjkl scroll  q quit";
+
+/// Steps through `simulation` in the terminal until the user quits,
+/// playing it at one step per `delay`.
+pub fn interact<S: Simulation>(simulation: S, style: &Style, delay: Duration) -> io::Result<()> {
+    let mut out = io::stdout();
+    let _terminal = Terminal::take_over(&mut out)?;
+    let mut viewer = Viewer {
+        stepper: Stepper::new(simulation),
+        playing: false,
+        count: None,
+        scroll: (0, 0),
+    };
+    let mut next_step = Instant::now() + delay;
+    loop {
+        viewer.draw(&mut out, style)?;
+        let timeout = if viewer.playing {
+            next_step.saturating_duration_since(Instant::now())
+        } else {
+            Duration::from_secs(3600)
+        };
+        if event::poll(timeout)? {
+            match event::read()? {
+                Event::Key(key) if key.kind != KeyEventKind::Release => {
+                    if !viewer.press(key) {
+                        return Ok(());
+                    }
+                    next_step = Instant::now() + delay;
+                }
+                _ => {}
+            }
+        } else if viewer.playing {
+            viewer.playing = viewer.stepper.forward(1);
+            next_step = Instant::now() + delay;
+        }
+    }
+}
+
+/// Puts the terminal in raw mode on its alternate screen, and puts it back
+/// when dropped.
+struct Terminal;
+
+impl Terminal {
+    fn take_over(out: &mut impl Write) -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        queue!(out, terminal::EnterAlternateScreen, cursor::Hide)?;
+        out.flush()?;
+        Ok(Terminal)
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        let mut out = io::stdout();
+        let _ = queue!(out, cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = out.flush();
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+struct Viewer<S> {
+    stepper: Stepper<S>,
+    playing: bool,
+    /// A number typed before a command, like the 10 in `10g`.
+    count: Option<usize>,
+    /// The cell at the top left of the screen.
+    scroll: (usize, usize),
+}
+
+impl<S: Simulation> Viewer<S> {
+    /// Handles a key, returning `false` if it was to quit.
+    fn press(&mut self, key: KeyEvent) -> bool {
+        let count = self.count.take();
+        let (x, y) = self.scroll;
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+            KeyCode::Char(digit @ '0'..='9') => {
+                let digit = digit.to_digit(10).unwrap() as usize;
+                self.count = Some(count.unwrap_or(0).saturating_mul(10).saturating_add(digit));
+            }
+            KeyCode::Char(' ') => self.playing = !self.playing,
+            KeyCode::Right | KeyCode::Char('.') => {
+                self.playing = false;
+                self.stepper.forward(count.unwrap_or(1));
+            }
+            KeyCode::Left | KeyCode::Char(',') => {
+                self.playing = false;
+                self.stepper.back(count.unwrap_or(1));
+            }
+            KeyCode::Char('g') => {
+                self.playing = false;
+                self.stepper.jump(count.unwrap_or(0));
+            }
+            KeyCode::Char('h') => self.scroll = (x.saturating_sub(8), y),
+            KeyCode::Char('l') => self.scroll = (x + 8, y),
+            KeyCode::Char('k') => self.scroll = (x, y.saturating_sub(8)),
+            KeyCode::Char('j') => self.scroll = (x, y + 8),
+            _ => {}
+        }
+        true
+    }
+
+    /// Draws as much of the current frame as fits, with the status and help
+    /// lines below it.
+    fn draw(&mut self, out: &mut impl Write, style: &Style) -> io::Result<()> {
+        let (columns, rows) = terminal::size()?;
+        let frame = self.stepper.state().frame();
+        let width = frame.width().min(usize::from(columns));
+        let height = frame.height().min(usize::from(rows).saturating_sub(2) * 2);
+        self.scroll = (
+            self.scroll.0.min(frame.width() - width),
+            self.scroll.1.min(frame.height() - height),
+        );
+        let (x0, y0) = self.scroll;
+        let visible = Grid::from_fn(width, height, |(x, y)| frame[(x0 + x, y0 + y)]);
+
+        queue!(out, cursor::MoveTo(0, 0))?;
+        for line in visualize::ansi_lines(style.palette, &visible) {
+            write!(out, "{}", line)?;
+            queue!(out, terminal::Clear(ClearType::UntilNewLine))?;
+            write!(out, "\r\n")?;
+        }
+        let end = match self.stepper.end() {
+            Some(end) => format!("/{}", end),
+            None => String::new(),
+        };
+        let state = if self.playing { "playing" } else { "paused" };
+        let count = self.count.map_or_else(String::new, |n| format!("  {}_", n));
+        write!(
+            out,
+            "step {}{} [{}]  {}{}",
+            self.stepper.step(),
+            end,
+            state,
+            self.stepper.state().status(),
+            count
+        )?;
+        queue!(out, terminal::Clear(ClearType::UntilNewLine))?;
+        write!(out, "\r\n{}", HELP)?;
+        queue!(out, terminal::Clear(ClearType::FromCursorDown))?;
+        out.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::visualize::Frame;
+
+    /// Counts up to a limit, remembering every number it has been.
+    #[derive(Clone)]
+    struct Counter {
+        history: Vec<usize>,
+        limit: usize,
+    }
+
+    impl Simulation for Counter {
+        fn step(&mut self) -> bool {
+            let n = self.history.len();
+            if n > self.limit {
+                return false;
+            }
+            self.history.push(n);
+            true
+        }
+
+        fn frame(&self) -> Frame {
+            Grid::new(1, 1, self.history.len() as u8)
+        }
+    }
+
+    fn counter(limit: usize) -> Stepper<Counter> {
+        Stepper::new(Counter {
+            history: vec![0],
+            limit,
+        })
+    }
+
+    #[test]
+    fn goes_back_and_forth() {
+        let mut stepper = counter(1000);
+        assert!(stepper.forward(500));
+        stepper.back(3);
+        assert_eq!(stepper.step(), 497);
+        assert_eq!(stepper.state().history, (0..498).collect::<Vec<_>>());
+        stepper.jump(123);
+        assert_eq!(stepper.state().history.len(), 124);
+        stepper.jump(0);
+        assert_eq!(stepper.state().history, [0]);
+        assert!(stepper.snapshots.len() <= SNAPSHOTS);
+    }
+
+    #[test]
+    fn stops_at_the_end() {
+        let mut stepper = counter(10);
+        assert_eq!(stepper.end(), None);
+        assert!(!stepper.forward(20));
+        assert_eq!((stepper.step(), stepper.end()), (10, Some(10)));
+        stepper.jump(50);
+        assert_eq!(stepper.step(), 10);
+        stepper.back(20);
+        assert_eq!(stepper.step(), 0);
+    }
+}
@@ -0,0 +1,137 @@
+use crate::{
+    parse,
+    registry::{Part, Puzzle},
+    worker::{self, Failure},
+};
+use std::{fmt, time::Duration};
+
+/// The expected answers for one puzzle input.
+///
+/// Answers files have a `[part1]` and a `[part2]` section, each followed by
+/// the answer on as many lines as it needs. Either section may be missing if
+/// the answer isn't known.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> Self {
+        let mut answers = Answers::default();
+        let mut section = None;
+        for line in text.lines() {
+            match line.trim() {
+                "[part1]" => section = Some(answers.part1.get_or_insert_with(String::new)),
+                "[part2]" => section = Some(answers.part2.get_or_insert_with(String::new)),
+                _ => {
+                    if let Some(answer) = section.as_mut() {
+                        answer.push_str(line);
+                        answer.push('\n');
+                    }
+                }
+            }
+        }
+        answers.part1 = answers.part1.map(|answer| normalize(&answer));
+        answers.part2 = answers.part2.map(|answer| normalize(&answer));
+        answers
+    }
+
+    pub fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+}
+
+/// Strips the whitespace that doesn't matter when comparing answers: blank
+/// lines around them and spaces at the ends of lines.
+pub fn normalize(answer: &str) -> String {
+    let lines: Vec<_> = answer.lines().map(str::trim_end).collect();
+    lines.join("\n").trim_matches('\n').to_string()
+}
+
+/// An answer short enough for a table cell, standing in for drawings.
+pub fn abbreviate(answer: &str) -> String {
+    match answer.lines().count() {
+        0 | 1 => answer.to_string(),
+        lines => format!("({} lines)", lines),
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Check {
+    Correct(String),
+    Wrong {
+        expected: String,
+        actual: String,
+    },
+    /// There's no expected answer to compare against.
+    Unknown(String),
+    Failed(Failure),
+    /// Never reached because part 1 failed.
+    Skipped,
+}
+
+impl Check {
+    pub fn is_ok(&self) -> bool {
+        matches!(self, Check::Correct(_) | Check::Unknown(_))
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Check::Correct(_) => "ok".fmt(f),
+            Check::Wrong { expected, actual } => {
+                write!(f, "WRONG, expected {} but got {}", expected, actual)
+            }
+            Check::Unknown(actual) => write!(f, "no expected answer, got {}", actual),
+            Check::Failed(Failure::TimedOut) => "TIMED OUT".fmt(f),
+            Check::Failed(Failure::Panicked(_)) => "PANICKED".fmt(f),
+            Check::Skipped => "skipped".fmt(f),
+        }
+    }
+}
+
+/// Solves both parts of `puzzle` and compares them against `answers`,
+/// giving up on a part that takes longer than `timeout`.
+pub fn verify(
+    puzzle: &'static Puzzle,
+    input: String,
+    answers: &Answers,
+    timeout: Duration,
+) -> parse::Result<Vec<(Part, Check)>> {
+    puzzle.parse(&input)?;
+    let (actual, failure) = worker::collect(timeout, move |tx| {
+        let parsed = puzzle.parse(&input).expect("input was already parsed");
+        for &part in &Part::BOTH {
+            if tx
+                .send(normalize(&puzzle.solve(&*parsed, part).to_string()))
+                .is_err()
+            {
+                return;
+            }
+        }
+    });
+    let failed_part = actual.len();
+    let mut actual = actual.into_iter();
+    Ok(Part::BOTH
+        .iter()
+        .enumerate()
+        .map(|(i, &part)| {
+            let check = match (actual.next(), answers.get(part), &failure) {
+                (Some(actual), Some(expected), _) if actual == expected => Check::Correct(actual),
+                (Some(actual), Some(expected), _) => Check::Wrong {
+                    expected: expected.to_string(),
+                    actual,
+                },
+                (Some(actual), None, _) => Check::Unknown(actual),
+                (None, _, Some(failure)) if i == failed_part => Check::Failed(failure.clone()),
+                (None, _, _) => Check::Skipped,
+            };
+            (part, check)
+        })
+        .collect())
+}
@@ -0,0 +1,322 @@
+use crate::grid::Grid;
+use std::{
+    convert::TryFrom,
+    io::{self, Write},
+    iter,
+    str::FromStr,
+    thread,
+    time::Duration,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+/// One picture of a day's state, as indices into its palette.
+pub type Frame = Grid<u8>;
+
+/// How a day's frames look.
+#[derive(Clone, Copy, Debug)]
+pub struct Style {
+    /// The color for each value a frame's cells can have.
+    pub palette: &'static [Rgb],
+    /// How long each frame of an animation is shown for.
+    pub delay: Duration,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// An animation of every frame.
+    Gif,
+    /// Just the last frame.
+    Png,
+    /// Every frame drawn over the last one in a truecolor terminal.
+    Ansi,
+}
+
+impl Format {
+    pub const NAMES: [&'static str; 3] = ["gif", "png", "ansi"];
+
+    pub fn extension(self) -> Option<&'static str> {
+        match self {
+            Format::Gif => Some("gif"),
+            Format::Png => Some("png"),
+            Format::Ansi => None,
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "gif" => Ok(Format::Gif),
+            "png" => Ok(Format::Png),
+            "ansi" => Ok(Format::Ansi),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Options {
+    pub format: Format,
+    /// How many pixels wide and high each cell is in an image.
+    pub scale: usize,
+    /// Overrides the style's delay between frames.
+    pub delay: Option<Duration>,
+    /// Stops after this many frames.
+    pub frames: Option<usize>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            format: Format::Gif,
+            scale: 1,
+            delay: None,
+            frames: None,
+        }
+    }
+}
+
+/// A day's state that moves on a step at a time, and can be drawn after
+/// each one.
+pub trait Simulation: Clone {
+    /// Moves on one step, returning `false` without changing anything if
+    /// the simulation is over.
+    fn step(&mut self) -> bool;
+    fn frame(&self) -> Frame;
+    /// A line about the state that the picture doesn't show, like how many
+    /// units are left.
+    fn status(&self) -> String {
+        String::new()
+    }
+}
+
+/// The simulation's frame as it is now, then after each step until it ends.
+pub fn frames<S: Simulation>(mut simulation: S) -> impl Iterator<Item = Frame> {
+    let initial = simulation.frame();
+    iter::once(initial).chain(iter::from_fn(move || {
+        if simulation.step() {
+            Some(simulation.frame())
+        } else {
+            None
+        }
+    }))
+}
+
+/// Draws `frames` to `out` in the format `options` asks for.
+pub fn write(
+    out: impl Write,
+    style: &Style,
+    frames: impl IntoIterator<Item = Frame>,
+    options: &Options,
+) -> io::Result<()> {
+    let frames = frames
+        .into_iter()
+        .take(options.frames.unwrap_or(usize::MAX));
+    let delay = options.delay.unwrap_or(style.delay);
+    let scale = options.scale;
+    match options.format {
+        Format::Gif => write_gif(out, style.palette, frames.map(|f| scaled(f, scale)), delay),
+        Format::Png => {
+            let last = frames.last().ok_or_else(nothing_to_draw)?;
+            write_png(out, style.palette, &scaled(last, scale))
+        }
+        Format::Ansi => write_ansi(out, style.palette, frames, delay),
+    }
+}
+
+fn nothing_to_draw() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, "there are no frames to draw")
+}
+
+fn scaled(frame: Frame, scale: usize) -> Frame {
+    if scale == 1 {
+        return frame;
+    }
+    Grid::from_fn(frame.width() * scale, frame.height() * scale, |(x, y)| {
+        frame[(x / scale, y / scale)]
+    })
+}
+
+fn colors(palette: &[Rgb]) -> Vec<u8> {
+    palette
+        .iter()
+        .flat_map(|&Rgb(r, g, b)| vec![r, g, b])
+        .collect()
+}
+
+fn pixels(frame: &Frame) -> Vec<u8> {
+    frame.cells().cloned().collect()
+}
+
+fn write_gif(
+    mut out: impl Write,
+    palette: &[Rgb],
+    frames: impl Iterator<Item = Frame>,
+    delay: Duration,
+) -> io::Result<()> {
+    use gif::{Encoder, Repeat, SetParameter};
+
+    let mut frames = frames.peekable();
+    let first = frames.peek().ok_or_else(nothing_to_draw)?;
+    let too_big = || io::Error::new(io::ErrorKind::InvalidInput, "frames are too big for a GIF");
+    let width = u16::try_from(first.width()).map_err(|_| too_big())?;
+    let height = u16::try_from(first.height()).map_err(|_| too_big())?;
+    let delay = (delay.as_millis() / 10).min(u128::from(u16::MAX)) as u16;
+    let mut encoder = Encoder::new(&mut out, width, height, &colors(palette))?;
+    encoder.set(Repeat::Infinite)?;
+    for frame in frames {
+        assert_eq!(
+            (frame.width(), frame.height()),
+            (width.into(), height.into()),
+            "every frame should be the same size"
+        );
+        encoder.write_frame(&gif::Frame {
+            width,
+            height,
+            delay,
+            buffer: pixels(&frame).into(),
+            ..gif::Frame::default()
+        })?;
+    }
+    Ok(())
+}
+
+fn write_png(out: impl Write, palette: &[Rgb], frame: &Frame) -> io::Result<()> {
+    let too_big = || {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the frame is too big for a PNG",
+        )
+    };
+    let width = u32::try_from(frame.width()).map_err(|_| too_big())?;
+    let height = u32::try_from(frame.height()).map_err(|_| too_big())?;
+    let mut encoder = png::Encoder::new(out, width, height);
+    encoder.set_color(png::ColorType::Indexed);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_palette(colors(palette));
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&pixels(frame))?;
+    Ok(())
+}
+
+/// Draws two rows of cells per line of text, as the foreground and
+/// background of `▀`, moving the cursor back up to draw each frame over the
+/// one before.
+fn write_ansi(
+    mut out: impl Write,
+    palette: &[Rgb],
+    frames: impl Iterator<Item = Frame>,
+    delay: Duration,
+) -> io::Result<()> {
+    let mut lines = 0;
+    for frame in frames {
+        if lines > 0 {
+            out.flush()?;
+            thread::sleep(delay);
+            write!(out, "\x1b[{}A", lines)?;
+        }
+        let text = ansi_lines(palette, &frame);
+        for line in &text {
+            writeln!(out, "{}", line)?;
+        }
+        lines = text.len();
+    }
+    out.flush()
+}
+
+/// The lines of text [`write_ansi`] draws `frame` as, without line endings.
+pub(crate) fn ansi_lines(palette: &[Rgb], frame: &Frame) -> Vec<String> {
+    let rows: Vec<_> = frame.rows().collect();
+    rows.chunks(2)
+        .map(|pair| {
+            let mut line = String::new();
+            for (x, &top) in pair[0].iter().enumerate() {
+                let Rgb(r, g, b) = palette[usize::from(top)];
+                line += &format!("\x1b[38;2;{};{};{}m", r, g, b);
+                match pair.get(1) {
+                    Some(bottom) => {
+                        let Rgb(r, g, b) = palette[usize::from(bottom[x])];
+                        line += &format!("\x1b[48;2;{};{};{}m▀", r, g, b);
+                    }
+                    None => line += "\x1b[49m▀",
+                }
+            }
+            line + "\x1b[0m"
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STYLE: Style = Style {
+        palette: &[Rgb(0, 0, 0), Rgb(0xff, 0x80, 0)],
+        delay: Duration::from_millis(0),
+    };
+
+    fn frames() -> Vec<Frame> {
+        vec![
+            Grid::from_fn(3, 2, |(x, _)| (x == 0) as u8),
+            Grid::from_fn(3, 2, |(x, y)| (x == y) as u8),
+        ]
+    }
+
+    #[test]
+    fn png_holds_the_last_frame() {
+        let mut out = Vec::new();
+        let options = Options {
+            format: Format::Png,
+            scale: 2,
+            ..Options::default()
+        };
+        write(&mut out, &STYLE, frames(), &options).unwrap();
+        let mut decoder = png::Decoder::new(&out[..]);
+        decoder.set_transformations(png::Transformations::IDENTITY);
+        let (info, mut reader) = decoder.read_info().unwrap();
+        assert_eq!((info.width, info.height), (6, 4));
+        let mut pixels = vec![0; info.buffer_size()];
+        reader.next_frame(&mut pixels).unwrap();
+        assert_eq!(&pixels[..6], [1, 1, 0, 0, 0, 0]);
+        assert_eq!(&pixels[12..18], [0, 0, 1, 1, 0, 0]);
+    }
+
+    #[test]
+    fn gif_holds_every_frame() {
+        let mut out = Vec::new();
+        write(&mut out, &STYLE, frames(), &Options::default()).unwrap();
+        let mut decoder = gif::Decoder::new(&out[..]).read_info().unwrap();
+        let mut count = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!((frame.width, frame.height), (3, 2));
+            count += 1;
+        }
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn ansi_draws_over_the_last_frame() {
+        let mut out = Vec::new();
+        let options = Options {
+            format: Format::Ansi,
+            frames: Some(2),
+            ..Options::default()
+        };
+        let frames = frames().into_iter().cycle();
+        write(&mut out, &STYLE, frames, &options).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out.matches('▀').count(), 6);
+        assert_eq!(out.matches("\x1b[1A").count(), 1);
+        assert!(out.starts_with("\x1b[38;2;255;128;0m\x1b[48;2;255;128;0m▀"));
+    }
+
+    #[test]
+    fn no_frames() {
+        let error = write(Vec::new(), &STYLE, Vec::new(), &Options::default()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+}
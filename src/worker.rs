@@ -0,0 +1,73 @@
+use std::{
+    any::Any,
+    sync::mpsc::{self, RecvTimeoutError, Sender},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Why a job stopped before finishing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Failure {
+    TimedOut,
+    /// The job panicked, with the panic's message.
+    Panicked(String),
+}
+
+/// Runs `job` on a thread of its own and collects the values it sends, so
+/// that a day that hangs or panics can't take the caller down with it.
+///
+/// Gives up if `timeout` passes without a new value arriving. Rust can't
+/// cancel a thread, so an abandoned job keeps running in the background
+/// until the process exits.
+pub fn collect<T, F>(timeout: Duration, job: F) -> (Vec<T>, Option<Failure>)
+where
+    T: Send + 'static,
+    F: FnOnce(Sender<T>) + Send + 'static,
+{
+    collect_with(job, |_| timeout)
+}
+
+/// Like [`collect`], but gives up at `deadline` however recently a value
+/// arrived.
+pub fn collect_until<T, F>(deadline: Instant, job: F) -> (Vec<T>, Option<Failure>)
+where
+    T: Send + 'static,
+    F: FnOnce(Sender<T>) + Send + 'static,
+{
+    collect_with(job, |now| deadline.saturating_duration_since(now))
+}
+
+/// Collects the values `job` sends, giving up once `wait` of the time it's
+/// called at passes without one.
+fn collect_with<T, F>(job: F, wait: impl Fn(Instant) -> Duration) -> (Vec<T>, Option<Failure>)
+where
+    T: Send + 'static,
+    F: FnOnce(Sender<T>) + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+    let worker = thread::spawn(move || job(tx));
+    let mut values = Vec::new();
+    loop {
+        match rx.recv_timeout(wait(Instant::now())) {
+            Ok(value) => values.push(value),
+            Err(RecvTimeoutError::Timeout) => return (values, Some(Failure::TimedOut)),
+            Err(RecvTimeoutError::Disconnected) => {
+                let failure = worker
+                    .join()
+                    .err()
+                    .map(|payload| Failure::Panicked(panic_message(&*payload)));
+                return (values, failure);
+            }
+        }
+    }
+}
+
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "(no message)".to_string()
+    }
+}
@@ -0,0 +1,70 @@
+use advent_of_code_2018::{
+    answer::Answer,
+    day01::Day01,
+    day02::Day02,
+    day03::Day03,
+    day04::Day04,
+    day05::Day05,
+    day06::Day06,
+    day07::Day07,
+    day08::Day08,
+    day09::Day09,
+    day10::Day10,
+    day11::Day11,
+    day12::Day12,
+    day13::Day13,
+    day14::Day14,
+    day15::Day15,
+    day16::Day16,
+    day17::Day17,
+    day18::Day18,
+    day19::Day19,
+    verify::{normalize, Answers},
+    Solution,
+};
+
+fn check_part1<S: Solution>() {
+    let answers = Answers::parse(S::ANSWERS);
+    let actual: Answer = S::part1(&S::parse(S::INPUT).unwrap()).into();
+    assert_eq!(normalize(&actual.to_string()), answers.part1.unwrap());
+}
+
+fn check_part2<S: Solution>() {
+    let answers = Answers::parse(S::ANSWERS);
+    let actual: Answer = S::part2(&S::parse(S::INPUT).unwrap()).into();
+    assert_eq!(normalize(&actual.to_string()), answers.part2.unwrap());
+}
+
+macro_rules! answer_tests {
+    ($($name:ident: $day:ty,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                check_part1::<$day>();
+                check_part2::<$day>();
+            }
+        )*
+    };
+}
+
+answer_tests! {
+    day01: Day01,
+    day02: Day02,
+    day03: Day03,
+    day04: Day04,
+    day05: Day05,
+    day06: Day06,
+    day07: Day07,
+    day08: Day08,
+    day09: Day09,
+    day10: Day10,
+    day11: Day11,
+    day12: Day12,
+    day13: Day13,
+    day14: Day14,
+    day15: Day15,
+    day16: Day16,
+    day17: Day17,
+    day18: Day18,
+    day19: Day19,
+}